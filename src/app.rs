@@ -1,19 +1,37 @@
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
+use chrono::Local;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use ratatui::prelude::*;
+use ratatui::{
+    layout::Flex,
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+use rust_i18n::t;
 
 use crate::{
-    config::Config,
+    config::{Config, FrameDumpFormat},
     event::{Event, EventHandler},
+    frame_dump, log, preset,
+    rotator::Rotator,
+    session::Session,
     shared_state::SharedState,
     tui::Tui,
     widgets::{
+        about::{About, AboutState},
+        context_menu::{self, ContextMenu, ContextMenuState},
         information::{self, InformationState},
         keymap::Keymap,
+        log_panel::LogPanel,
+        next_pass::{self, NextPass, NextPassState},
+        objects_table::{self, ObjectsTable, ObjectsTableState},
+        planes::{self, PlanesState},
         satellite_groups::{self, SatelliteGroups, SatelliteGroupsState},
         sky::{self, SkyState},
         tabs::{self, Tabs, TabsState},
         timeline::{self, Timeline, TimelineState},
+        toast::{Toast, ToastState},
         world_map::{self, WorldMap, WorldMapState},
     },
 };
@@ -25,17 +43,37 @@ pub struct App {
     pub running: bool,
     pub states: States,
     tui: Tui<CrosstermBackend<std::io::Stdout>>,
+    /// Format of the dumped screen contents.
+    frame_dump_format: FrameDumpFormat,
+    /// Whether to dump the screen contents after the next render.
+    dump_frame: bool,
+    /// Whether the terminal was too small for the layout at the last render.
+    too_small: bool,
+    /// The duration without input after which the application quits.
+    idle_timeout: Option<Duration>,
+    /// The time of the last key or mouse input.
+    last_input: Instant,
 }
 
 impl App {
+    /// The terminal width below which the compact layout is used.
+    const COMPACT_WIDTH: u16 = 100;
+    /// The minimum terminal size for the layout.
+    const MIN_SIZE: Size = Size::new(40, 12);
+
     /// Creates a new `App` with the given configuration.
     pub fn with_config(config: Config) -> Result<Self> {
         let backend = CrosstermBackend::new(std::io::stdout());
         let terminal = Terminal::new(backend)?;
-        let events = EventHandler::new();
+        let events = EventHandler::new(Duration::from_millis(config.app.update_interval_ms));
         let tui = Tui::new(terminal, events);
         Ok(Self {
             running: true,
+            frame_dump_format: config.app.frame_dump_format,
+            dump_frame: false,
+            too_small: false,
+            idle_timeout: Self::idle_timeout(config.app.idle_timeout_min),
+            last_input: Instant::now(),
             states: States::with_config(config),
             tui,
         })
@@ -48,12 +86,31 @@ impl App {
         // The main loop.
         while self.running {
             let event = self.tui.events.next().await?;
+            // Only user input counts as activity
+            if matches!(event, Event::Key(_) | Event::Mouse(_)) {
+                self.last_input = Instant::now();
+            }
             self.handle_event(event).await?;
+
+            if let Some(timeout) = self.idle_timeout
+                && self.last_input.elapsed() >= timeout
+            {
+                self.request_exit();
+            }
         }
 
+        // Cancel in-flight fetch tasks before exiting.
+        self.states.satellite_groups_state.cancel_all_loading();
+
         self.tui.deinit()
     }
 
+    /// Returns the idle timeout for the given number of minutes, where `0`
+    /// disables it.
+    fn idle_timeout(minutes: u64) -> Option<Duration> {
+        (minutes > 0).then(|| Duration::from_secs(minutes * 60))
+    }
+
     /// Set running to false to quit the application.
     fn request_exit(&mut self) {
         self.running = false;
@@ -61,69 +118,243 @@ impl App {
 
     /// Renders the terminal interface.
     fn render(&mut self) -> Result<()> {
-        self.tui.terminal.draw(|frame| {
-            let horizontal = Layout::horizontal([Constraint::Percentage(80), Constraint::Min(25)]);
-            let [left_area, right_area] = horizontal.areas(frame.area());
-
-            let left_vertical = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]);
-            let [left_top_area, left_bottom_area] = left_vertical.areas(left_area);
+        let completed_frame = self.tui.terminal.draw(|frame| {
+            let area = frame.area();
+            // Panels would get empty areas on tiny terminals
+            self.too_small =
+                area.width < Self::MIN_SIZE.width || area.height < Self::MIN_SIZE.height;
+            if self.too_small {
+                let [message_area] = Layout::vertical([Constraint::Length(2)])
+                    .flex(Flex::Center)
+                    .areas(area);
+                Paragraph::new(t!(
+                    "terminal_too_small",
+                    width = Self::MIN_SIZE.width,
+                    height = Self::MIN_SIZE.height
+                ))
+                .centered()
+                .wrap(Wrap { trim: true })
+                .render(message_area, frame.buffer_mut());
+                return;
+            }
 
-            WorldMap {
-                state: &mut self.states.world_map_state,
-                shared: &self.states.shared,
+            if self.states.clean_mode {
+                // Leave only the map, without borders and titles
+                WorldMap {
+                    state: &mut self.states.world_map_state,
+                    shared: &self.states.shared,
+                    focused: false,
+                    clean: true,
+                }
+                .render(area, frame.buffer_mut());
+            } else {
+                Self::render_panels(&mut self.states, area, frame.buffer_mut());
             }
-            .render(left_top_area, frame.buffer_mut());
 
-            Timeline {
-                state: &mut self.states.timeline_state,
-                shared: &self.states.shared,
+            if self.states.objects_table_state.is_open() {
+                ObjectsTable {
+                    state: &mut self.states.objects_table_state,
+                    shared: &self.states.shared,
+                    world_map_state: &self.states.world_map_state,
+                    timeline_state: &self.states.timeline_state,
+                }
+                .render(area, frame.buffer_mut());
             }
-            .render(left_bottom_area, frame.buffer_mut());
 
-            let vertical = Layout::vertical([Constraint::Percentage(60), Constraint::Fill(1)]);
-            let [right_top_area, right_bottom_area] = vertical.areas(right_area);
+            Toast {
+                state: &self.states.toast_state,
+            }
+            .render(frame.area(), frame.buffer_mut());
 
-            Tabs {
-                state: &mut self.states.tab_state,
-                shared: &self.states.shared,
-                sky_state: &mut self.states.sky_state,
-                information_state: &mut self.states.information_state,
+            ContextMenu {
+                state: &mut self.states.context_menu_state,
             }
-            .render(right_top_area, frame.buffer_mut());
+            .render(frame.area(), frame.buffer_mut());
 
-            SatelliteGroups {
-                state: &mut self.states.satellite_groups_state,
+            if self.states.about_state.is_open() {
+                About {
+                    state: &self.states.about_state,
+                    shared: &self.states.shared,
+                    groups: &self.states.satellite_groups_state,
+                }
+                .render(frame.area(), frame.buffer_mut());
             }
-            .render(right_bottom_area, frame.buffer_mut());
 
             if self.states.show_keymap {
                 Keymap.render(frame.area(), frame.buffer_mut());
             }
         })?;
+
+        if std::mem::take(&mut self.dump_frame) {
+            let contents = frame_dump::dump(completed_frame.buffer, self.frame_dump_format);
+            let path = std::env::current_dir().unwrap_or_default().join(format!(
+                "tracker-{}.{}",
+                Local::now().format("%Y%m%d-%H%M%S"),
+                self.frame_dump_format.extension()
+            ));
+            match std::fs::write(&path, contents) {
+                Ok(()) => self
+                    .states
+                    .toast_state
+                    .show(t!("toast-frame_dumped", path = path.display())),
+                Err(_) => self.states.toast_state.show(t!("toast-frame_dump_failed")),
+            }
+        }
         Ok(())
     }
 
+    /// Renders the panels of the interface.
+    fn render_panels(states: &mut States, area: Rect, buf: &mut Buffer) {
+        // On small terminals, stack the panels below the map instead of
+        // squeezing them into a side column.
+        let compact = area.width < Self::COMPACT_WIDTH;
+        let (left_area, right_area) = if compact {
+            let vertical = Layout::vertical([Constraint::Fill(1), Constraint::Percentage(40)]);
+            let [top_area, bottom_area] = vertical.areas(area);
+            (top_area, bottom_area)
+        } else {
+            let horizontal = Layout::horizontal([Constraint::Percentage(80), Constraint::Min(25)]);
+            let [left_area, right_area] = horizontal.areas(area);
+            (left_area, right_area)
+        };
+
+        let left_vertical = Layout::vertical([Constraint::Min(0), Constraint::Length(3)]);
+        let [left_top_area, left_bottom_area] = left_vertical.areas(left_area);
+
+        WorldMap {
+            state: &mut states.world_map_state,
+            shared: &states.shared,
+            focused: states.focused == Panel::WorldMap,
+            clean: false,
+        }
+        .render(left_top_area, buf);
+
+        // Display the next pass on the top border of the world map
+        NextPass {
+            state: &states.next_pass_state,
+            shared: &states.shared,
+        }
+        .render(
+            left_top_area
+                .inner(Margin::new(1, 0))
+                .rows()
+                .next()
+                .unwrap_or_default(),
+            buf,
+        );
+
+        Timeline {
+            state: &mut states.timeline_state,
+            shared: &states.shared,
+            focused: states.focused == Panel::Timeline,
+        }
+        .render(left_bottom_area, buf);
+
+        let right_layout = if compact {
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Fill(1)])
+        } else {
+            Layout::vertical([Constraint::Percentage(60), Constraint::Fill(1)])
+        };
+        let [right_top_area, right_bottom_area] = right_layout.areas(right_area);
+
+        Tabs {
+            state: &mut states.tab_state,
+            shared: &states.shared,
+            sky_state: &mut states.sky_state,
+            information_state: &mut states.information_state,
+            planes_state: &mut states.planes_state,
+            focused: states.focused == Panel::Tabs,
+        }
+        .render(right_top_area, buf);
+
+        SatelliteGroups {
+            state: &mut states.satellite_groups_state,
+            focused: states.focused == Panel::SatelliteGroups,
+        }
+        .render(right_bottom_area, buf);
+
+        if states.show_log {
+            LogPanel.render(left_area, buf);
+        }
+    }
+
     async fn handle_event(&mut self, event: Event) -> Result<()> {
+        self.route_event(event)?;
+        // Any other event may change the selected object, the time or the
+        // minimum pass elevation
+        if !matches!(event, Event::Render) {
+            self.states.shared.update_pass_cache();
+        }
+        Ok(())
+    }
+
+    /// Passes the event to the application and the widgets that handle it.
+    fn route_event(&mut self, event: Event) -> Result<()> {
         match event {
             Event::Render => self.render()?,
             Event::Key(event) => self.handle_key_events(event),
             _ => {}
         }
 
-        // Block input events when keymap is shown
-        if self.states.show_keymap {
+        // Block mouse events while the layout is not displayed
+        if self.too_small && matches!(event, Event::Mouse(_)) {
+            return Ok(());
+        }
+
+        // Block input events when a popup is shown
+        if self.states.show_keymap || self.states.about_state.is_open() {
             match event {
                 Event::Key(_) | Event::Mouse(_) => return Ok(()),
                 _ => {}
             }
         }
 
-        world_map::handle_event(event, &mut self.states)?;
-        satellite_groups::handle_event(event, &mut self.states)?;
-        tabs::handle_event(event, &mut self.states)?;
-        information::handle_event(event, &mut self.states)?;
-        sky::handle_event(event, &mut self.states)?;
-        timeline::handle_event(event, &mut self.states)
+        // Send input events only to the context menu when it is open
+        if self.states.context_menu_state.is_open() {
+            match event {
+                Event::Key(_) | Event::Mouse(_) => {
+                    return context_menu::handle_event(event, &mut self.states);
+                }
+                _ => {}
+            }
+        }
+
+        // Send input events only to the objects table when it is open
+        if self.states.objects_table_state.is_open() {
+            match event {
+                Event::Key(_) | Event::Mouse(_) => {
+                    return objects_table::handle_event(event, &mut self.states);
+                }
+                _ => {}
+            }
+        }
+
+        // Route the event to the focused panel first.
+        let focused = self.states.focused;
+        for panel in
+            std::iter::once(focused).chain(Panel::ALL.into_iter().filter(|p| *p != focused))
+        {
+            // Only the world map is displayed in clean mode
+            if self.states.clean_mode
+                && panel != Panel::WorldMap
+                && matches!(event, Event::Mouse(_))
+            {
+                continue;
+            }
+            // Keys consumed by a panel are not passed to the others
+            if panel.handle_event(event, &mut self.states)? {
+                break;
+            }
+        }
+
+        next_pass::handle_event(event, &mut self.states);
+        if let Event::Update = event {
+            objects_table::handle_event(event, &mut self.states)?;
+            if let Some(rotator) = &mut self.states.rotator {
+                rotator.update(&self.states.shared);
+            }
+        }
+        Ok(())
     }
 
     fn handle_key_events(&mut self, event: KeyEvent) {
@@ -140,37 +371,211 @@ impl App {
             KeyCode::Char('?') => {
                 self.states.show_keymap = !self.states.show_keymap;
             }
-            // Close keymap popup on `Esc`.
+            // Toggle log panel.
+            KeyCode::Char('L') => {
+                self.states.show_log = !self.states.show_log;
+            }
+            // Toggle the objects table.
+            KeyCode::Char('O') => {
+                self.states.objects_table_state.toggle();
+            }
+            // Toggle the about popup.
+            KeyCode::Char('D') => {
+                self.states.about_state.toggle();
+            }
+            // Toggle clean mode.
+            KeyCode::Char('C') => {
+                self.states.clean_mode = !self.states.clean_mode;
+            }
+            // Dump the screen contents to a file.
+            KeyCode::Char('p') => {
+                self.dump_frame = true;
+            }
+            // Reload the configuration on `Ctrl-R`.
+            KeyCode::Char('r') if event.modifiers == KeyModifiers::CONTROL => {
+                match Config::load() {
+                    Ok(config) => {
+                        self.frame_dump_format = config.app.frame_dump_format;
+                        self.idle_timeout = Self::idle_timeout(config.app.idle_timeout_min);
+                        self.tui.events.set_update_interval(Duration::from_millis(
+                            config.app.update_interval_ms,
+                        ));
+                        self.states.reconfigure(config);
+                        self.states.toast_state.show(t!("toast-config_reloaded"));
+                    }
+                    Err(e) => {
+                        log::push(format!("Failed to reload configuration: {e}"));
+                        self.states
+                            .toast_state
+                            .show(t!("toast-config_reload_failed"));
+                    }
+                }
+            }
+            // Focus the next/previous panel.
+            KeyCode::Char('w') => {
+                self.states.focused = self.states.focused.next();
+            }
+            KeyCode::Char('W') => {
+                self.states.focused = self.states.focused.previous();
+            }
+            // Save the view preset of the slot on `Alt-<digit>`, recall it on `<digit>`.
+            KeyCode::Char(digit @ '1'..='9') => {
+                let slot = digit as u8 - b'0';
+                if event.modifiers.contains(KeyModifiers::ALT) {
+                    preset::save(slot, &mut self.states);
+                } else {
+                    preset::recall(slot, &mut self.states);
+                }
+            }
+            // Toggle blinking text.
+            KeyCode::Char('b') => {
+                self.states.shared.disable_blink = !self.states.shared.disable_blink;
+            }
+            // Close popups on `Esc`.
             KeyCode::Esc => {
                 self.states.show_keymap = false;
+                self.states.show_log = false;
+                self.states.objects_table_state.close();
+                self.states.about_state.close();
+                self.states.toast_state.dismiss();
             }
             _ => {}
         }
     }
 }
 
+/// A panel of the interface that can receive the keyboard focus.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub enum Panel {
+    #[default]
+    WorldMap,
+    Timeline,
+    Tabs,
+    SatelliteGroups,
+}
+
+impl Panel {
+    /// All panels, in the focus cycle order.
+    const ALL: [Self; 4] = [
+        Self::WorldMap,
+        Self::Timeline,
+        Self::Tabs,
+        Self::SatelliteGroups,
+    ];
+
+    /// Returns the next panel in the cycle.
+    pub fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    /// Returns the previous panel in the cycle.
+    pub fn previous(self) -> Self {
+        Self::ALL[(self as usize + Self::ALL.len() - 1) % Self::ALL.len()]
+    }
+
+    /// Passes the event to the widgets of the panel.
+    ///
+    /// Returns whether a widget consumed the event, i.e. handled the key.
+    fn handle_event(self, event: Event, states: &mut States) -> Result<bool> {
+        match self {
+            Self::WorldMap => world_map::handle_event(event, states),
+            Self::Timeline => timeline::handle_event(event, states),
+            Self::Tabs => {
+                let mut consumed = tabs::handle_event(event, states)?;
+                consumed |= information::handle_event(event, states)?;
+                consumed |= sky::handle_event(event, states)?;
+                consumed |= planes::handle_event(event, states)?;
+                Ok(consumed)
+            }
+            Self::SatelliteGroups => satellite_groups::handle_event(event, states),
+        }
+    }
+}
+
 pub struct States {
     pub shared: SharedState,
     pub world_map_state: WorldMapState,
     pub satellite_groups_state: SatelliteGroupsState,
     pub tab_state: TabsState,
     pub information_state: InformationState,
+    pub planes_state: PlanesState,
     pub sky_state: SkyState,
     pub timeline_state: TimelineState,
+    pub toast_state: ToastState,
+    pub context_menu_state: ContextMenuState,
+    pub objects_table_state: ObjectsTableState,
+    pub about_state: AboutState,
+    pub next_pass_state: NextPassState,
+    /// The antenna rotator controller, if enabled.
+    pub rotator: Option<Rotator>,
     pub show_keymap: bool,
+    pub show_log: bool,
+    /// Whether only the world map is displayed, without borders and titles.
+    pub clean_mode: bool,
+    /// The panel that has the keyboard focus.
+    pub focused: Panel,
 }
 
 impl States {
     pub fn with_config(config: Config) -> Self {
+        let session = Session::load();
+        let shared = SharedState::with_config(config.app, config.sky.clone(), session.favorites);
+        let world_map_state =
+            WorldMapState::with_config(config.world_map, shared.ground_station.as_ref());
+        let satellite_groups_state =
+            SatelliteGroupsState::with_config(config.satellite_groups, &shared.favorites);
         Self {
-            shared: SharedState::with_config(config.sky.clone()),
-            world_map_state: WorldMapState::with_config(config.world_map),
-            satellite_groups_state: SatelliteGroupsState::with_config(config.satellite_groups),
-            tab_state: Default::default(),
-            information_state: Default::default(),
+            shared,
+            world_map_state,
+            satellite_groups_state,
+            tab_state: TabsState::with_config(config.planes),
+            planes_state: Default::default(),
+            information_state: InformationState::with_config(config.information),
             sky_state: SkyState::with_config(config.sky),
             timeline_state: TimelineState::with_config(config.timeline),
+            toast_state: Default::default(),
+            context_menu_state: Default::default(),
+            objects_table_state: Default::default(),
+            about_state: Default::default(),
+            next_pass_state: Default::default(),
+            rotator: Rotator::with_config(config.rotator),
             show_keymap: false,
+            show_log: false,
+            clean_mode: false,
+            focused: Panel::default(),
         }
     }
+
+    /// Applies a new configuration, keeping the selection, the simulation time
+    /// and the settings changed at runtime, i.e. the blinking text, the
+    /// minimum pass elevation and the sky chart rotation.
+    ///
+    /// The objects are reloaded from the selected groups.
+    pub fn reconfigure(&mut self, config: Config) {
+        let favorites = std::mem::take(&mut self.shared.favorites);
+        let old_shared = std::mem::replace(
+            &mut self.shared,
+            SharedState::with_config(config.app, config.sky.clone(), favorites),
+        );
+        self.shared.selected_object = old_shared.selected_object;
+        self.shared.hovered_object = old_shared.hovered_object;
+        self.shared.reference_object = old_shared.reference_object;
+        self.shared.multi_selection = old_shared.multi_selection;
+        self.shared.time = old_shared.time;
+        self.shared.preview_offset = old_shared.preview_offset;
+        self.shared.disable_blink = old_shared.disable_blink;
+        self.shared
+            .pass_cache
+            .set_min_elevation(old_shared.pass_cache.min_elevation());
+
+        self.world_map_state
+            .reconfigure(config.world_map, self.shared.ground_station.as_ref());
+        self.satellite_groups_state
+            .reconfigure(config.satellite_groups, &self.shared.favorites);
+        self.information_state = InformationState::with_config(config.information);
+        self.sky_state.reconfigure(config.sky);
+        self.timeline_state.reconfigure(config.timeline);
+        self.rotator = Rotator::with_config(config.rotator);
+        self.tab_state.reconfigure(config.planes);
+    }
 }
@@ -1,5 +1,9 @@
-use ratatui::style::Color;
-use serde::Deserialize;
+use std::{fmt::Write as _, ops::RangeInclusive, path::PathBuf};
+
+use anyhow::{Context as _, Result};
+use chrono::Utc;
+use ratatui::{style::Color, symbols::Marker};
+use serde::{Deserialize, Deserializer, de};
 
 use crate::coordinates::Lla;
 
@@ -7,10 +11,130 @@ use crate::coordinates::Lla;
 #[derive(Clone, Default, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct Config {
+    pub app: AppConfig,
     pub world_map: WorldMapConfig,
     pub satellite_groups: SatelliteGroupsConfig,
+    pub information: InformationConfig,
     pub sky: SkyConfig,
     pub timeline: TimelineConfig,
+    pub rotator: RotatorConfig,
+    pub planes: PlanesConfig,
+}
+
+impl Config {
+    /// Loads the configuration from `~/.config/tracker/config.toml`, or
+    /// returns the default configuration if the file does not exist.
+    pub fn load() -> Result<Self> {
+        let path = Self::path().context("failed to get home directory")?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config: Self = toml::from_str(&content)?;
+        Ok(config)
+    }
+
+    /// Returns the path of the configuration file, which may not exist.
+    pub fn path() -> Option<PathBuf> {
+        Some(std::env::home_dir()?.join(".config/tracker/config.toml"))
+    }
+}
+
+/// General configuration for the application.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    #[serde(deserialize_with = "deserialize_time_format")]
+    pub time_format: String,
+    pub disable_blink: bool,
+    pub frame_dump_format: FrameDumpFormat,
+    pub marker: CanvasMarker,
+    #[serde(deserialize_with = "deserialize_update_interval")]
+    pub update_interval_ms: u64,
+    pub idle_timeout_min: u64,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            time_format: "%Y-%m-%d %H:%M:%S".into(),
+            disable_blink: false,
+            frame_dump_format: FrameDumpFormat::Ansi,
+            marker: CanvasMarker::Braille,
+            update_interval_ms: 100,
+            idle_timeout_min: 0,
+        }
+    }
+}
+
+/// Format of the dumped screen contents.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FrameDumpFormat {
+    /// Plain text without colors.
+    Text,
+    /// Text with ANSI escape codes for colors and modifiers.
+    Ansi,
+}
+
+impl FrameDumpFormat {
+    /// Returns the file extension used for this format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Ansi => "ans",
+        }
+    }
+}
+
+/// Marker used to draw the canvases.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanvasMarker {
+    /// Braille patterns, with the highest resolution.
+    Braille,
+    /// A dot per cell.
+    Dot,
+    /// A full block per cell.
+    Block,
+    /// Half blocks, with twice the vertical resolution.
+    HalfBlock,
+}
+
+impl From<CanvasMarker> for Marker {
+    fn from(marker: CanvasMarker) -> Self {
+        match marker {
+            CanvasMarker::Braille => Self::Braille,
+            CanvasMarker::Dot => Self::Dot,
+            CanvasMarker::Block => Self::Block,
+            CanvasMarker::HalfBlock => Self::HalfBlock,
+        }
+    }
+}
+
+/// Deserializes a time format string, rejecting invalid format specifiers.
+fn deserialize_time_format<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let format = String::deserialize(deserializer)?;
+    let mut buf = String::new();
+    write!(buf, "{}", Utc::now().format(&format))
+        .map_err(|_| de::Error::custom(format!("invalid time format `{format}`")))?;
+    Ok(format)
+}
+
+fn deserialize_update_interval<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<u64, D::Error> {
+    const BOUNDS_MS: RangeInclusive<u64> = 100..=5000;
+
+    let interval = u64::deserialize(deserializer)?;
+    if !BOUNDS_MS.contains(&interval) {
+        return Err(de::Error::custom(format!(
+            "update interval of {interval} ms is not between {} and {} ms",
+            BOUNDS_MS.start(),
+            BOUNDS_MS.end()
+        )));
+    }
+    Ok(interval)
 }
 
 /// Configuration for the world map widget.
@@ -18,28 +142,211 @@ pub struct Config {
 #[serde(default, deny_unknown_fields)]
 pub struct WorldMapConfig {
     pub follow_object: bool,
+    pub auto_follow_on_select: bool,
     pub follow_smoothing: f64,
     pub show_terminator: bool,
+    pub show_twilight: bool,
+    pub fill_land: bool,
     pub show_visibility_area: bool,
+    pub visibility_min_elevation_deg: f64,
+    pub visibility_earth_radius_km: Option<f64>,
+    pub show_shadow_track: bool,
+    pub show_inertial_track: bool,
+    pub show_altitude_bands: bool,
+    pub show_trail: bool,
+    pub show_station_coordinates: bool,
+    pub show_heading_arrow: bool,
+    pub show_az_el: bool,
+    pub show_inclination_band: bool,
+    pub context_menu: bool,
+    pub trail_length_mins: i64,
     pub lon_delta_deg: f64,
+    pub line_subdivisions: usize,
+    pub initial_lon_offset: f64,
+    pub initial_center_on_station: bool,
+    pub max_rendered_objects: usize,
+    pub max_selection_distance: Option<f64>,
+    pub labels: LabelMode,
+    pub filter: ObjectFilterConfig,
+    pub show_elevation_filter: bool,
+    pub filter_min_elevation_deg: f64,
+    pub highlight_pattern: Option<String>,
+    pub show_builtin_map: bool,
+    pub coastline_geojson: Option<PathBuf>,
     pub map_color: Color,
+    pub land_color: Color,
     pub trajectory_color: Color,
+    pub shadow_track_color: Color,
+    pub trail_color: Color,
     pub terminator_color: Color,
+    pub twilight_color: Color,
     pub visibility_area_color: Color,
+    pub highlight_color: Color,
+    pub reference_color: Color,
+    pub multi_selection_color: Color,
 }
 
 impl Default for WorldMapConfig {
     fn default() -> Self {
         Self {
             follow_object: true,
+            auto_follow_on_select: false,
             follow_smoothing: 0.3,
             show_terminator: true,
+            show_twilight: false,
+            fill_land: false,
             show_visibility_area: true,
+            visibility_min_elevation_deg: 0.0,
+            visibility_earth_radius_km: None,
+            show_shadow_track: false,
+            show_inertial_track: false,
+            show_altitude_bands: false,
+            show_trail: false,
+            show_station_coordinates: false,
+            show_heading_arrow: false,
+            show_az_el: false,
+            show_inclination_band: false,
+            context_menu: false,
+            trail_length_mins: 10,
             lon_delta_deg: 10.0,
+            line_subdivisions: 1,
+            initial_lon_offset: 0.0,
+            initial_center_on_station: false,
+            max_rendered_objects: 0,
+            max_selection_distance: None,
+            labels: LabelMode::default(),
+            filter: ObjectFilterConfig::default(),
+            show_elevation_filter: false,
+            filter_min_elevation_deg: 0.0,
+            highlight_pattern: None,
+            show_builtin_map: true,
+            coastline_geojson: None,
             map_color: Color::Gray,
+            land_color: Color::Indexed(236),
             trajectory_color: Color::LightBlue,
+            shadow_track_color: Color::Blue,
+            trail_color: Color::White,
             terminator_color: Color::DarkGray,
+            twilight_color: Color::Blue,
             visibility_area_color: Color::Yellow,
+            highlight_color: Color::LightMagenta,
+            reference_color: Color::LightCyan,
+            multi_selection_color: Color::LightGreen,
+        }
+    }
+}
+
+/// Ranges of orbital parameters outside which objects are hidden on the world
+/// map.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ObjectFilterConfig {
+    pub min_altitude_km: Option<f64>,
+    pub max_altitude_km: Option<f64>,
+    pub min_inclination_deg: Option<f64>,
+    pub max_inclination_deg: Option<f64>,
+}
+
+impl ObjectFilterConfig {
+    /// Returns whether any bound is set.
+    pub fn is_active(&self) -> bool {
+        self.describe().is_some()
+    }
+
+    /// Returns a compact description of the set bounds, e.g.
+    /// `200-2000 km, ≥50°`, or `None` if no bound is set.
+    pub fn describe(&self) -> Option<String> {
+        let range = |min: Option<f64>, max: Option<f64>, unit: &str| match (min, max) {
+            (Some(min), Some(max)) => Some(format!("{min}-{max}{unit}")),
+            (Some(min), None) => Some(format!("≥{min}{unit}")),
+            (None, Some(max)) => Some(format!("≤{max}{unit}")),
+            (None, None) => None,
+        };
+        let ranges: Vec<_> = [
+            range(self.min_altitude_km, self.max_altitude_km, " km"),
+            range(self.min_inclination_deg, self.max_inclination_deg, "°"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!ranges.is_empty()).then(|| ranges.join(", "))
+    }
+}
+
+/// Which object labels are displayed on the world map.
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelMode {
+    /// Display labels of all objects.
+    #[default]
+    All,
+    /// Display only the labels of the selected, hovered and highlighted
+    /// objects.
+    SelectedOnly,
+    /// Display only the labels of the selected and hovered objects.
+    None,
+}
+
+impl LabelMode {
+    /// Returns the next mode in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::SelectedOnly,
+            Self::SelectedOnly => Self::None,
+            Self::None => Self::All,
+        }
+    }
+}
+
+/// Order of the satellite groups in the list.
+///
+/// Favorites are always listed first.
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupOrder {
+    /// The order of the configuration.
+    #[default]
+    Config,
+    /// Alphabetical order of the labels.
+    Name,
+    /// Selected groups first.
+    Selected,
+}
+
+impl GroupOrder {
+    /// Returns the next order in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Config => Self::Name,
+            Self::Name => Self::Selected,
+            Self::Selected => Self::Config,
+        }
+    }
+}
+
+/// Order of the loaded objects.
+#[derive(Clone, Copy, PartialEq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ObjectOrder {
+    /// The order in which the objects were fetched.
+    #[default]
+    Fetch,
+    /// Alphabetical order of the names.
+    Name,
+    /// Ascending current altitude.
+    Altitude,
+    /// Descending current elevation above the ground station.
+    Elevation,
+}
+
+impl ObjectOrder {
+    /// Returns the next order in the cycle.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Fetch => Self::Name,
+            Self::Name => Self::Altitude,
+            Self::Altitude => Self::Elevation,
+            Self::Elevation => Self::Fetch,
         }
     }
 }
@@ -49,6 +356,12 @@ impl Default for WorldMapConfig {
 #[serde(default, deny_unknown_fields)]
 pub struct SatelliteGroupsConfig {
     pub cache_lifetime_mins: u64,
+    pub max_startup_cache_age_mins: Option<u64>,
+    pub deselect_missing_object: bool,
+    pub group_order: GroupOrder,
+    pub object_order: ObjectOrder,
+    pub show_object_counts: bool,
+    pub show_cached_object_counts: bool,
     pub groups: Vec<GroupConfig>,
 }
 
@@ -58,6 +371,10 @@ pub struct GroupConfig {
     pub label: String,
     pub id: Option<String>,
     pub group: Option<String>,
+    pub file: Option<PathBuf>,
+    /// Whether the group is selected on startup.
+    #[serde(default)]
+    pub selected: bool,
 }
 
 impl GroupConfig {
@@ -66,6 +383,8 @@ impl GroupConfig {
             label,
             id: Some(cospar_id),
             group: None,
+            file: None,
+            selected: false,
         }
     }
 
@@ -74,6 +393,8 @@ impl GroupConfig {
             label,
             id: None,
             group: Some(group_name),
+            file: None,
+            selected: false,
         }
     }
 }
@@ -82,6 +403,12 @@ impl Default for SatelliteGroupsConfig {
     fn default() -> Self {
         Self {
             cache_lifetime_mins: 2 * 60,
+            max_startup_cache_age_mins: None,
+            deselect_missing_object: true,
+            group_order: GroupOrder::Config,
+            object_order: ObjectOrder::Fetch,
+            show_object_counts: true,
+            show_cached_object_counts: false,
             groups: vec![
                 // Specific objects of interest
                 GroupConfig::with_id("ISS".into(), "1998-067A".into()),
@@ -144,11 +471,81 @@ impl Default for SatelliteGroupsConfig {
     }
 }
 
+/// Configuration for the information widget.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InformationConfig {
+    pub details_url: String,
+    pub show_advanced: bool,
+    pub geocentric_latitude: bool,
+    pub close_approach_hours: i64,
+    pub close_approach_threshold_km: f64,
+}
+
+impl Default for InformationConfig {
+    fn default() -> Self {
+        Self {
+            details_url: "https://celestrak.org/satcat/table-satcat.php?CATNR={norad_id}".into(),
+            show_advanced: false,
+            geocentric_latitude: false,
+            close_approach_hours: 24,
+            close_approach_threshold_km: 10.0,
+        }
+    }
+}
+
 /// Configuration for the sky widget.
-#[derive(Clone, Default, Deserialize)]
+#[derive(Clone, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct SkyConfig {
     pub ground_station: Option<GroundStationConfig>,
+    pub use_gpsd: bool,
+    pub gpsd_address: String,
+    pub pass_look_ahead_hours: i64,
+    pub min_pass_elevation_deg: f64,
+    pub show_elevation_plot: bool,
+    pub track_palette: TrackPalette,
+    pub track_elevation_thresholds_deg: [f64; 2],
+    pub rotation_deg: f64,
+}
+
+impl Default for SkyConfig {
+    fn default() -> Self {
+        Self {
+            ground_station: None,
+            use_gpsd: false,
+            gpsd_address: "127.0.0.1:2947".into(),
+            pass_look_ahead_hours: 24,
+            min_pass_elevation_deg: 0.0,
+            show_elevation_plot: true,
+            track_palette: TrackPalette::Solid,
+            track_elevation_thresholds_deg: [10.0, 30.0],
+            rotation_deg: 0.0,
+        }
+    }
+}
+
+/// Palette used to color the sky track by elevation.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackPalette {
+    /// A single color, regardless of the elevation.
+    Solid,
+    /// Red near the horizon, yellow, then green near the zenith.
+    TrafficLight,
+    /// Blue near the horizon, cyan, then white near the zenith.
+    Cool,
+}
+
+impl TrackPalette {
+    /// Returns the colors for the low, medium and high elevations.
+    pub fn colors(self) -> [Color; 3] {
+        match self {
+            Self::Solid => [Color::LightBlue; 3],
+            Self::TrafficLight => [Color::LightRed, Color::LightYellow, Color::LightGreen],
+            Self::Cool => [Color::Blue, Color::LightCyan, Color::White],
+        }
+    }
 }
 
 #[derive(Clone, Deserialize)]
@@ -163,10 +560,69 @@ pub struct GroundStationConfig {
 #[serde(default, deny_unknown_fields)]
 pub struct TimelineConfig {
     pub time_delta_mins: i64,
+    pub doppler_frequency_mhz: Option<f64>,
+    pub time_zone: TimeZoneMode,
+    pub preview_on_hover: bool,
+    pub recenter_on_select: bool,
+    pub axis_color: Color,
+    pub hour_mark_color: Color,
+    pub current_time_color: Color,
+    pub pass_color: Color,
 }
 
 impl Default for TimelineConfig {
     fn default() -> Self {
-        Self { time_delta_mins: 1 }
+        Self {
+            time_delta_mins: 1,
+            doppler_frequency_mhz: None,
+            time_zone: TimeZoneMode::Local,
+            preview_on_hover: false,
+            recenter_on_select: false,
+            axis_color: Color::DarkGray,
+            hour_mark_color: Color::White,
+            current_time_color: Color::LightRed,
+            pass_color: Color::LightYellow,
+        }
+    }
+}
+
+/// Time zones in which the timeline displays times.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeZoneMode {
+    /// The local time zone.
+    #[default]
+    Local,
+    /// UTC.
+    Utc,
+    /// Both the local time zone and UTC.
+    Both,
+}
+
+/// Configuration for the planes tab.
+#[derive(Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PlanesConfig {
+    pub enabled: bool,
+}
+
+/// Configuration for the antenna rotator control.
+#[derive(Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RotatorConfig {
+    pub enabled: bool,
+    pub host: String,
+    pub port: u16,
+    pub min_elevation_deg: f64,
+}
+
+impl Default for RotatorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".into(),
+            port: 4533,
+            min_elevation_deg: 0.0,
+        }
     }
 }
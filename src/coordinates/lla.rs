@@ -15,7 +15,7 @@ pub struct Lla {
     pub lat: f64,
     /// Longitude in degrees.
     pub lon: f64,
-    /// Altitude in km.
+    /// Altitude in km, negative below the ellipsoid surface.
     pub alt: f64,
 }
 
@@ -23,7 +23,6 @@ impl Lla {
     pub fn new(lat: f64, lon: f64, alt: f64) -> Self {
         debug_assert!((-90.0..=90.0).contains(&lat));
         debug_assert!((-180.0..=180.0).contains(&lon));
-        debug_assert!(alt >= 0.0);
         Lla { lat, lon, alt }
     }
 
@@ -92,6 +91,17 @@ impl Lla {
         (az_deg, el_deg)
     }
 
+    /// Computes the straight-line distance in km from the observer's position
+    /// to this point.
+    pub fn range(&self, observer: &Lla) -> f64 {
+        let obs_ecef = observer.to_ecef();
+        let tgt_ecef = self.to_ecef();
+        ((tgt_ecef.x - obs_ecef.x).powi(2)
+            + (tgt_ecef.y - obs_ecef.y).powi(2)
+            + (tgt_ecef.z - obs_ecef.z).powi(2))
+        .sqrt()
+    }
+
     /// Returns the city and country name.
     pub fn country_city(&self) -> (String, String) {
         let record = GEOCODER.search((self.lat, self.lon)).record;
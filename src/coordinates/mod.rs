@@ -7,13 +7,16 @@ pub use lla::Lla;
 pub use teme::Teme;
 
 /// WGS84 geodetic reference frame constants.
-mod wgs84 {
+pub mod wgs84 {
     /// Earth semi-major axis in kilometers.
     pub const A: f64 = 6378.137;
     /// Flattening factor.
     pub const F: f64 = 1.0 / 298.257223563;
     /// Earth semi-minor axis in kilometers.
     pub const B: f64 = A * (1.0 - F);
+    /// Earth mean radius in kilometers, the radius of a sphere approximating
+    /// the ellipsoid.
+    pub const MEAN_RADIUS: f64 = (2.0 * A + B) / 3.0;
     /// First eccentricity squared.
     pub const E2: f64 = 1.0 - (B * B) / (A * A);
     /// Second eccentricity squared.
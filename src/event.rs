@@ -3,7 +3,7 @@ use std::time::Duration;
 use anyhow::Result;
 use crossterm::event::{Event as CrosstermEvent, KeyEvent, MouseEvent};
 use futures::{FutureExt, StreamExt};
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 /// Terminal events.
 #[derive(Clone, Copy, Eq, PartialEq, Debug)]
@@ -22,17 +22,21 @@ pub struct EventHandler {
     sender: mpsc::UnboundedSender<Event>,
     /// Event receiver channel.
     receiver: mpsc::UnboundedReceiver<Event>,
+    /// Update interval sender.
+    update_interval_sender: watch::Sender<Duration>,
     /// Event handler thread.
     handler: tokio::task::JoinHandle<()>,
 }
 
 impl EventHandler {
-    /// Creates a new `EventHandler`.
-    pub fn new() -> Self {
-        const UPDATE_RATE: f64 = 10.0;
+    /// Creates a new `EventHandler` emitting update events every
+    /// `update_interval`, independently of the render rate.
+    pub fn new(update_interval: Duration) -> Self {
         const RENDER_RATE: f64 = 60.0;
 
-        let mut update_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / UPDATE_RATE));
+        let (update_interval_sender, mut update_interval_receiver) =
+            watch::channel(update_interval);
+        let mut update_interval = tokio::time::interval(update_interval);
         let mut render_interval = tokio::time::interval(Duration::from_secs_f64(1.0 / RENDER_RATE));
         let (sender, receiver) = mpsc::unbounded_channel();
         let sender_clone = sender.clone();
@@ -44,6 +48,10 @@ impl EventHandler {
                   _ = sender_clone.closed() => {
                     break;
                   }
+                  Ok(()) = update_interval_receiver.changed() => {
+                    let period = *update_interval_receiver.borrow_and_update();
+                    update_interval = tokio::time::interval(period);
+                  }
                   _ = update_interval.tick() => {
                     sender_clone.send(Event::Update).unwrap();
                   }
@@ -69,10 +77,16 @@ impl EventHandler {
         Self {
             sender,
             receiver,
+            update_interval_sender,
             handler,
         }
     }
 
+    /// Sets the interval at which update events are emitted.
+    pub fn set_update_interval(&self, update_interval: Duration) {
+        self.update_interval_sender.send_replace(update_interval);
+    }
+
     /// Receive the next event from the handler thread.
     ///
     /// This function will always block the current thread if
@@ -84,12 +98,6 @@ impl EventHandler {
     }
 }
 
-impl Default for EventHandler {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 impl Drop for EventHandler {
     fn drop(&mut self) {
         self.handler.abort();
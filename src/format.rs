@@ -0,0 +1,190 @@
+use chrono::Duration;
+
+/// Formats a number using the decimal and digit grouping separators of the
+/// current locale.
+///
+/// If `precision` is `None`, the shortest representation is used.
+pub fn format_number(value: f64, precision: Option<usize>) -> String {
+    let (decimal_separator, group_separator) = separators(&rust_i18n::locale());
+    let formatted = match precision {
+        Some(precision) => format!("{value:.precision$}"),
+        None => value.to_string(),
+    };
+
+    let (sign, unsigned) = match formatted.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", formatted.as_str()),
+    };
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+
+    let mut result = String::from(sign);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            result.push(group_separator);
+        }
+        result.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        result.push(decimal_separator);
+        result.push_str(fraction);
+    }
+    result
+}
+
+/// Formats a distance in km, with fewer decimals for larger distances.
+///
+/// The compact mode drops more decimals, for narrow panels.
+pub fn format_distance(km: f64, compact: bool) -> String {
+    let precision = match (km.abs(), compact) {
+        (..1_000.0, false) => 3,
+        (..10_000.0, false) => 2,
+        (_, false) => 1,
+        (..1_000.0, true) => 1,
+        (_, true) => 0,
+    };
+    format!("{} km", format_number(km, Some(precision)))
+}
+
+/// Formats a speed in km/s, or in m/s below 0.1 km/s, e.g. for the ground
+/// speed of geostationary objects.
+///
+/// The compact mode drops a decimal, for narrow panels.
+pub fn format_speed(km_per_second: f64, compact: bool) -> String {
+    let precision = if compact { 1 } else { 2 };
+    if km_per_second.abs() < 0.1 {
+        return format!(
+            "{} m/s",
+            format_number(km_per_second * 1000.0, Some(precision - 1))
+        );
+    }
+    format!("{} km/s", format_number(km_per_second, Some(precision)))
+}
+
+/// Formats an orbital period as hours, minutes and seconds followed by the
+/// decimal minutes, e.g. `1h 32m 45s (92.75 min)`.
+///
+/// The compact mode keeps only the decimal minutes, for narrow panels.
+pub fn format_period(period: Duration, compact: bool) -> String {
+    let minutes = format!(
+        "{} min",
+        format_number(period.as_seconds_f64() / 60.0, Some(2))
+    );
+    if compact {
+        return minutes;
+    }
+
+    let seconds = period.as_seconds_f64().round() as i64;
+    let (hours, minutes_part, seconds_part) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes_part}m {seconds_part}s ({minutes})")
+    } else {
+        format!("{minutes_part}m {seconds_part}s ({minutes})")
+    }
+}
+
+/// Formats a signed time offset as hours, minutes and seconds, omitting the
+/// leading zero units, e.g. `+1h 5m 0s` or `-30s`.
+pub fn format_offset(offset: Duration) -> String {
+    let sign = if offset < Duration::zero() { '-' } else { '+' };
+    let seconds = offset.num_seconds().abs();
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{sign}{hours}h {minutes}m {seconds}s")
+    } else if minutes > 0 {
+        format!("{sign}{minutes}m {seconds}s")
+    } else {
+        format!("{sign}{seconds}s")
+    }
+}
+
+/// Formats a right ascension in degrees as hours, minutes and seconds, e.g.
+/// `05h 34m 31.9s`.
+pub fn format_right_ascension(degrees: f64) -> String {
+    const TENTHS_PER_DAY: i64 = 24 * 36_000;
+
+    let tenths = ((degrees / 15.0 * 36_000.0).round() as i64).rem_euclid(TENTHS_PER_DAY);
+    format!(
+        "{:02}h {:02}m {:02}.{}s",
+        tenths / 36_000,
+        tenths / 600 % 60,
+        tenths / 10 % 60,
+        tenths % 10
+    )
+}
+
+/// Formats a declination in degrees as signed degrees, arcminutes and
+/// arcseconds, e.g. `+22° 00′ 52″`.
+pub fn format_declination(degrees: f64) -> String {
+    let sign = if degrees < 0.0 { '-' } else { '+' };
+    let arcseconds = (degrees.abs() * 3600.0).round() as i64;
+    format!(
+        "{sign}{:02}° {:02}′ {:02}″",
+        arcseconds / 3600,
+        arcseconds / 60 % 60,
+        arcseconds % 60
+    )
+}
+
+/// Returns the decimal and digit grouping separators of a locale.
+///
+/// Falls back to `.` and `,` for unknown locales.
+fn separators(locale: &str) -> (char, char) {
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    match language {
+        "de" | "es" => (',', '.'),
+        "fr" | "ru" => (',', ' '),
+        _ => ('.', ','),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_precision_adapts_to_magnitude() {
+        assert_eq!(separators("en"), ('.', ','));
+        assert_eq!(format_distance(408.1234, false), "408.123 km");
+        assert_eq!(format_distance(1_234.567, false), "1,234.57 km");
+        assert_eq!(format_distance(35_786.04, false), "35,786.0 km");
+        assert_eq!(format_distance(408.1234, true), "408.1 km");
+        assert_eq!(format_distance(35_786.04, true), "35,786 km");
+    }
+
+    #[test]
+    fn slow_speeds_are_formatted_in_meters_per_second() {
+        assert_eq!(format_speed(7.6612, false), "7.66 km/s");
+        assert_eq!(format_speed(0.0123, false), "12.3 m/s");
+        assert_eq!(format_speed(0.0123, true), "12 m/s");
+    }
+
+    #[test]
+    fn period_is_formatted_as_hours_minutes_seconds() {
+        let period = Duration::milliseconds(5_565_000);
+        assert_eq!(format_period(period, false), "1h 32m 45s (92.75 min)");
+        assert_eq!(format_period(period, true), "92.75 min");
+        let sidereal_day = Duration::milliseconds(86_164_091);
+        assert_eq!(
+            format_period(sidereal_day, false),
+            "23h 56m 4s (1,436.07 min)"
+        );
+    }
+
+    #[test]
+    fn equatorial_coordinates_are_sexagesimal() {
+        assert_eq!(format_right_ascension(83.633), "05h 34m 31.9s");
+        assert_eq!(format_right_ascension(359.99999), "00h 00m 00.0s");
+        assert_eq!(format_declination(22.0144), "+22° 00′ 52″");
+        assert_eq!(format_declination(-5.391), "-05° 23′ 28″");
+    }
+
+    #[test]
+    fn offset_omits_leading_zero_units() {
+        assert_eq!(format_offset(Duration::seconds(3_900)), "+1h 5m 0s");
+        assert_eq!(format_offset(Duration::seconds(-90)), "-1m 30s");
+        assert_eq!(format_offset(Duration::seconds(-30)), "-30s");
+    }
+}
@@ -0,0 +1,117 @@
+use std::fmt::Write as _;
+
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthStr;
+
+use crate::config::FrameDumpFormat;
+
+/// Converts the rendered buffer to text in the given format.
+pub fn dump(buf: &Buffer, format: FrameDumpFormat) -> String {
+    match format {
+        FrameDumpFormat::Text => to_text(buf),
+        FrameDumpFormat::Ansi => to_ansi(buf),
+    }
+}
+
+/// Converts the rendered buffer to plain text.
+fn to_text(buf: &Buffer) -> String {
+    let mut text = String::new();
+    for y in buf.area.top()..buf.area.bottom() {
+        let mut line = String::new();
+        for cell in row_cells(buf, y) {
+            line.push_str(cell.symbol());
+        }
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+/// Converts the rendered buffer to text with ANSI escape codes.
+fn to_ansi(buf: &Buffer) -> String {
+    let mut text = String::new();
+    for y in buf.area.top()..buf.area.bottom() {
+        let mut style = None;
+        for cell in row_cells(buf, y) {
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                text.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                style = Some(cell_style);
+            }
+            text.push_str(cell.symbol());
+        }
+        text.push_str("\x1b[0m\n");
+    }
+    text
+}
+
+/// Returns the cells of a row, skipping the cells covered by wide characters.
+fn row_cells(buf: &Buffer, y: u16) -> impl Iterator<Item = &buffer::Cell> {
+    let mut to_skip = 0;
+    (buf.area.left()..buf.area.right()).filter_map(move |x| {
+        if to_skip > 0 {
+            to_skip -= 1;
+            return None;
+        }
+        let cell = &buf[(x, y)];
+        to_skip = cell.symbol().width().saturating_sub(1);
+        Some(cell)
+    })
+}
+
+/// Returns the SGR escape sequence selecting the given style.
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    const MODIFIER_CODES: [(Modifier, u8); 9] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::RAPID_BLINK, 6),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+
+    let mut codes = String::from("0");
+    for (flag, code) in MODIFIER_CODES {
+        if modifier.contains(flag) {
+            write!(codes, ";{code}").unwrap();
+        }
+    }
+    write!(codes, ";{};{}", color_code(fg, 30), color_code(bg, 40)).unwrap();
+    format!("\x1b[{codes}m")
+}
+
+/// Returns the SGR parameters of a color, where `base` is 30 for foreground
+/// and 40 for background colors.
+fn color_code(color: Color, base: u8) -> String {
+    let named = |index: u8| {
+        if index < 8 {
+            (base + index).to_string()
+        } else {
+            (base + 60 + index - 8).to_string()
+        }
+    };
+    match color {
+        Color::Reset => (base + 9).to_string(),
+        Color::Black => named(0),
+        Color::Red => named(1),
+        Color::Green => named(2),
+        Color::Yellow => named(3),
+        Color::Blue => named(4),
+        Color::Magenta => named(5),
+        Color::Cyan => named(6),
+        Color::Gray => named(7),
+        Color::DarkGray => named(8),
+        Color::LightRed => named(9),
+        Color::LightGreen => named(10),
+        Color::LightYellow => named(11),
+        Color::LightBlue => named(12),
+        Color::LightMagenta => named(13),
+        Color::LightCyan => named(14),
+        Color::White => named(15),
+        Color::Indexed(index) => format!("{};5;{index}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+    }
+}
@@ -0,0 +1,86 @@
+use std::path::Path;
+
+use anyhow::Result;
+use serde_json::Value;
+
+/// Loads the lines of a GeoJSON file, as `(longitude, latitude)` pairs in
+/// degrees.
+///
+/// Line strings and the rings of polygons are loaded from geometries, features
+/// and collections of them. Other geometries are ignored.
+pub fn load_lines(path: &Path) -> Result<Vec<Vec<(f64, f64)>>> {
+    let json = std::fs::read_to_string(path)?;
+    parse_lines(&json)
+}
+
+/// Parses the lines of a GeoJSON document.
+fn parse_lines(json: &str) -> Result<Vec<Vec<(f64, f64)>>> {
+    let value: Value = serde_json::from_str(json)?;
+    let mut lines = Vec::new();
+    collect_lines(&value, &mut lines);
+    Ok(lines)
+}
+
+/// Collects the lines of a GeoJSON object recursively.
+fn collect_lines(value: &Value, lines: &mut Vec<Vec<(f64, f64)>>) {
+    let array = |key: &str| value[key].as_array().into_iter().flatten();
+    match value["type"].as_str() {
+        Some("FeatureCollection") => array("features").for_each(|f| collect_lines(f, lines)),
+        Some("Feature") => collect_lines(&value["geometry"], lines),
+        Some("GeometryCollection") => array("geometries").for_each(|g| collect_lines(g, lines)),
+        Some("LineString") => lines.extend(to_line(&value["coordinates"])),
+        Some("MultiLineString" | "Polygon") => {
+            lines.extend(array("coordinates").filter_map(to_line))
+        }
+        Some("MultiPolygon") => lines.extend(
+            array("coordinates")
+                .filter_map(Value::as_array)
+                .flatten()
+                .filter_map(to_line),
+        ),
+        _ => {}
+    }
+}
+
+/// Converts GeoJSON coordinates to a line, or `None` if they are invalid.
+fn to_line(coordinates: &Value) -> Option<Vec<(f64, f64)>> {
+    coordinates
+        .as_array()?
+        .iter()
+        .map(|position| Some((position[0].as_f64()?, position[1].as_f64()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_nested_geometries() {
+        let json = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "geometry": { "type": "LineString", "coordinates": [[0, 0], [10, 5]] }
+                },
+                {
+                    "type": "Feature",
+                    "geometry": {
+                        "type": "MultiPolygon",
+                        "coordinates": [[[[1, 1], [2, 1], [2, 2], [1, 1]]]]
+                    }
+                },
+                { "type": "Feature", "geometry": { "type": "Point", "coordinates": [3, 3] } }
+            ]
+        }"#;
+        let lines = parse_lines(json).unwrap();
+        assert_eq!(
+            lines,
+            vec![
+                vec![(0.0, 0.0), (10.0, 5.0)],
+                vec![(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 1.0)],
+            ]
+        );
+    }
+}
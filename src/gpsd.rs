@@ -0,0 +1,87 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::mpsc,
+};
+
+use crate::coordinates::Lla;
+
+/// The minimum interval between two position updates.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+/// The delay before reconnecting to gpsd after the connection is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A report sent by gpsd.
+///
+/// See <https://gpsd.gitlab.io/gpsd/gpsd_json.html>.
+#[derive(Deserialize)]
+struct Report {
+    class: String,
+    /// Latitude in degrees.
+    lat: Option<f64>,
+    /// Longitude in degrees.
+    lon: Option<f64>,
+    /// Altitude above the WGS84 ellipsoid in meters.
+    #[serde(rename = "altHAE")]
+    alt_hae: Option<f64>,
+    /// Altitude above the mean sea level in meters.
+    alt: Option<f64>,
+}
+
+/// Spawns an async task that polls the current position from gpsd.
+///
+/// The task reconnects if the connection is lost, and stops once the returned
+/// receiver is dropped.
+///
+/// # Arguments
+///
+/// * `address` - The address of the gpsd daemon, e.g. `127.0.0.1:2947`.
+pub fn spawn_poller(address: String) -> mpsc::UnboundedReceiver<Lla> {
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while !tx.is_closed() {
+            let _ = poll_position(&address, &tx).await;
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+    rx
+}
+
+/// Connects to gpsd and sends position fixes until the connection is closed.
+async fn poll_position(address: &str, tx: &mpsc::UnboundedSender<Lla>) -> std::io::Result<()> {
+    let mut stream = TcpStream::connect(address).await?;
+    stream
+        .write_all(br#"?WATCH={"enable":true,"json":true};"#)
+        .await?;
+
+    let mut lines = BufReader::new(stream).lines();
+    let mut last_update: Option<Instant> = None;
+    while let Some(line) = lines.next_line().await? {
+        let Ok(report) = serde_json::from_str::<Report>(&line) else {
+            continue;
+        };
+        // Only time-position-velocity reports with a fix contain a position
+        let (Some(lat), Some(lon)) = (report.lat, report.lon) else {
+            continue;
+        };
+        if report.class != "TPV" {
+            continue;
+        }
+        if last_update.is_some_and(|time| time.elapsed() < UPDATE_INTERVAL) {
+            continue;
+        }
+
+        let alt_m = report.alt_hae.or(report.alt).unwrap_or(0.0);
+        if tx
+            .send(Lla::new(lat, lon, (alt_m / 1000.0).max(0.0)))
+            .is_err()
+        {
+            break;
+        }
+        last_update = Some(Instant::now());
+    }
+    Ok(())
+}
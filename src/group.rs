@@ -1,12 +1,22 @@
-use std::{fmt::Display, sync::LazyLock, time::Duration};
+use std::{
+    fmt::Display,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::LazyLock,
+    time::Duration,
+};
 
+use futures::stream::{self, StreamExt};
 use tokio::fs;
 
-use crate::config::GroupConfig;
+use crate::{config::GroupConfig, log};
 
 /// The timeout duration for HTTP requests.
 const HTTP_TIMEOUT_SECS: u64 = 10;
 
+/// The maximum number of concurrent requests when fetching a list file.
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
 /// The shared HTTP client used for making requests.
 static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
     reqwest::Client::builder()
@@ -22,67 +32,236 @@ static HTTP_CLIENT: LazyLock<reqwest::Client> = LazyLock::new(|| {
 pub struct Group {
     label: String,
     identifier: Identifier,
+    /// Directory of the cache file instead of [`Group::cache_dir`], for tests.
+    cache_dir: Option<PathBuf>,
 }
 
 impl Group {
+    /// The name of the provider the elements are fetched from.
+    pub const PROVIDER: &str = "CelesTrak";
+    /// The endpoint the elements are fetched from.
+    pub const ELEMENTS_URL: &str = "https://celestrak.org/NORAD/elements/gp.php";
+
+    /// Creates a group containing the single object with the given NORAD ID.
+    pub fn from_norad_id(norad_id: u64) -> Self {
+        Self {
+            label: norad_id.to_string(),
+            identifier: Identifier::NoradId(norad_id),
+            cache_dir: None,
+        }
+    }
+
     /// Returns the label.
     pub fn label(&self) -> &str {
         &self.label
     }
 
+    /// Returns the file listing the objects of the group, if any.
+    pub fn list_file(&self) -> Option<&Path> {
+        match &self.identifier {
+            Identifier::File(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    /// Sets the label.
+    pub fn set_label(&mut self, label: impl Into<String>) {
+        self.label = label.into();
+    }
+
     /// Returns SGP4 elements.
     ///
-    /// If cache is expired, fetches elements from <https://celestrak.org>.
-    /// Otherwise, reads elements from cache.
+    /// If cache is expired or corrupted, fetches elements from
+    /// <https://celestrak.org>. Otherwise, reads elements from cache.
+    ///
+    /// If fetching fails, falls back to the expired cache. Returns `None` if
+    /// neither is available.
     ///
     /// # Arguments
     ///
     /// * `cache_lifetime` - Duration for which the cache is considered valid.
     pub async fn get_elements(&self, cache_lifetime: Duration) -> Option<Vec<sgp4::Elements>> {
-        let cache_path = std::env::temp_dir().join(format!(
-            "tracker/{}.json",
-            self.identifier.to_string().to_lowercase()
-        ));
+        let cache_path = self.cache_path();
         fs::create_dir_all(cache_path.parent().unwrap())
             .await
             .unwrap();
 
         // Check if cache needs refresh (doesn't exist or expired)
-        let needs_refresh = !fs::try_exists(&cache_path).await.unwrap()
-            || fs::metadata(&cache_path)
-                .await
-                .unwrap()
-                .modified()
-                .unwrap()
-                .elapsed()
-                .unwrap()
-                > cache_lifetime;
-
-        if needs_refresh {
-            let elements = self.fetch_elements().await?;
-            let path = cache_path.clone();
-            let json = serde_json::to_string(&elements).unwrap();
-            fs::write(path, json).await.unwrap();
+        let needs_refresh = fs::metadata(&cache_path)
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_none_or(|age| age > cache_lifetime);
+
+        if !needs_refresh {
+            if let Some(elements) = Self::read_cache(&cache_path).await {
+                log::push(format!("Cache hit for {}", self.identifier));
+                return Some(elements);
+            }
+            // Remove the corrupted cache and fetch again
+            log::push(format!("Corrupted cache for {}", self.identifier));
+            let _ = fs::remove_file(&cache_path).await;
+        } else {
+            log::push(format!("Cache miss for {}", self.identifier));
         }
 
-        let json = fs::read_to_string(&cache_path).await.unwrap();
-        serde_json::from_str(&json).expect("failed to parse cache")
+        match &self.identifier {
+            Identifier::File(path) => log::push(format!(
+                "Fetching the objects listed in {} from celestrak.org",
+                path.display()
+            )),
+            identifier => log::push(format!("Fetching {identifier} from celestrak.org")),
+        }
+        let Some(elements) = self.fetch_elements().await else {
+            let elements = Self::read_cache(&cache_path).await;
+            if elements.is_some() {
+                log::push(format!("Using expired cache for {}", self.identifier));
+            }
+            return elements;
+        };
+        log::push(format!(
+            "Fetched {} elements for {}",
+            elements.len(),
+            self.identifier
+        ));
+        Self::write_cache(&cache_path, &elements).await;
+        Some(elements)
+    }
+
+    /// Returns the number of objects in the cache, regardless of its age.
+    ///
+    /// Returns `None` if the cache cannot be read or parsed.
+    pub async fn cached_object_count(&self) -> Option<usize> {
+        let json = fs::read_to_string(self.cache_path()).await.ok()?;
+        serde_json::from_str::<Vec<serde::de::IgnoredAny>>(&json)
+            .ok()
+            .map(|elements| elements.len())
+    }
+
+    /// Returns the directory of the cache files.
+    pub fn cache_dir() -> PathBuf {
+        std::env::temp_dir().join("tracker")
+    }
+
+    /// Returns the path of the cache file.
+    ///
+    /// The cache of a list file is keyed on a hash of its canonical path, so
+    /// files with the same name in different directories do not share it.
+    pub fn cache_path(&self) -> PathBuf {
+        let name = match &self.identifier {
+            Identifier::File(path) => {
+                let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                let mut hasher = DefaultHasher::new();
+                path.hash(&mut hasher);
+                format!("{}-{:016x}", self.identifier, hasher.finish())
+            }
+            identifier => identifier.to_string(),
+        };
+        let dir = self.cache_dir.clone().unwrap_or_else(Self::cache_dir);
+        dir.join(format!("{}.json", name.to_lowercase()))
+    }
+
+    /// Reads SGP4 elements from the cache file.
+    ///
+    /// Returns `None` if the cache cannot be read or parsed.
+    async fn read_cache(path: &Path) -> Option<Vec<sgp4::Elements>> {
+        let json = fs::read_to_string(path).await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Writes SGP4 elements to the cache file.
+    ///
+    /// The elements are written to a temporary file first and then renamed, so
+    /// that the cache file is never partially written.
+    async fn write_cache(path: &Path, elements: &[sgp4::Elements]) {
+        let json = serde_json::to_string(elements).unwrap();
+        let temp_path = path.with_extension("json.tmp");
+        if fs::write(&temp_path, json).await.is_ok() {
+            let _ = fs::rename(&temp_path, path).await;
+        }
     }
 
     /// Fetches SGP4 elements from <https://celestrak.org>.
     async fn fetch_elements(&self) -> Option<Vec<sgp4::Elements>> {
-        const URL: &str = "https://celestrak.org/NORAD/elements/gp.php";
+        match &self.identifier {
+            Identifier::File(path) => Self::fetch_file_elements(path).await,
+            identifier => Self::fetch_identifier_elements(identifier).await,
+        }
+    }
+
+    /// Fetches SGP4 elements of the objects listed in a file.
+    ///
+    /// The file contains one COSPAR ID or NORAD ID per line. Empty lines and
+    /// lines starting with `#` are ignored. Returns `None` if the file cannot
+    /// be read or no object could be fetched.
+    async fn fetch_file_elements(path: &Path) -> Option<Vec<sgp4::Elements>> {
+        let content = match fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(e) => {
+                log::push(format!("Failed to read {}: {e}", path.display()));
+                return None;
+            }
+        };
+
+        let mut identifiers: Vec<Identifier> = Vec::new();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let identifier = match line.parse() {
+                Ok(norad_id) => Identifier::NoradId(norad_id),
+                Err(_) => Identifier::CosparId(line.to_owned()),
+            };
+            if !identifiers.contains(&identifier) {
+                identifiers.push(identifier);
+            }
+        }
+
+        let results: Vec<_> = stream::iter(identifiers.iter().map(Self::fetch_identifier_elements))
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect()
+            .await;
+        let mut elements: Vec<sgp4::Elements> = Vec::new();
+        let mut found = 0;
+        for (identifier, result) in identifiers.iter().zip(results) {
+            match result {
+                Some(result) if !result.is_empty() => {
+                    found += 1;
+                    for element in result {
+                        // Skip objects listed by both COSPAR ID and NORAD ID
+                        if !elements.iter().any(|e| e.norad_id == element.norad_id) {
+                            elements.push(element);
+                        }
+                    }
+                }
+                _ => log::push(format!("No elements found for {identifier}")),
+            }
+        }
+        log::push(format!(
+            "Found {found} of {} IDs listed in {}",
+            identifiers.len(),
+            path.display()
+        ));
+        (found > 0).then_some(elements)
+    }
 
-        let mut request = HTTP_CLIENT.get(URL).query(&[("FORMAT", "json")]);
-        request = match &self.identifier {
+    /// Fetches SGP4 elements of a COSPAR ID, NORAD ID or group.
+    async fn fetch_identifier_elements(identifier: &Identifier) -> Option<Vec<sgp4::Elements>> {
+        let mut request = HTTP_CLIENT
+            .get(Self::ELEMENTS_URL)
+            .query(&[("FORMAT", "json")]);
+        request = match identifier {
             Identifier::CosparId(id) => request.query(&[("INTDES", id)]),
             Identifier::Group(group) => request.query(&[("GROUP", group)]),
+            Identifier::NoradId(id) => request.query(&[("CATNR", id)]),
+            Identifier::File(_) => unreachable!("files are fetched by their listed IDs"),
         };
 
         let response = match request.send().await {
             Ok(resp) => resp,
             Err(e) => {
-                eprintln!("Failed to fetch from celestrak.org: {}", e);
+                log::push(format!("Failed to fetch from celestrak.org: {e}"));
                 return None;
             }
         };
@@ -90,7 +269,7 @@ impl Group {
         match response.json().await {
             Ok(data) => Some(data),
             Err(e) => {
-                eprintln!("Failed to parse JSON from celestrak.org: {}", e);
+                log::push(format!("Failed to parse JSON from celestrak.org: {e}"));
                 None
             }
         }
@@ -105,16 +284,16 @@ impl PartialEq for Group {
 
 impl From<GroupConfig> for Group {
     fn from(config: GroupConfig) -> Self {
-        match (config.id, config.group) {
-            (Some(id), None) => Self {
-                label: config.label,
-                identifier: Identifier::CosparId(id),
-            },
-            (None, Some(group)) => Self {
-                label: config.label,
-                identifier: Identifier::Group(group),
-            },
+        let identifier = match (config.id, config.group, config.file) {
+            (Some(id), None, None) => Identifier::CosparId(id),
+            (None, Some(group), None) => Identifier::Group(group),
+            (None, None, Some(file)) => Identifier::File(file),
             _ => panic!("invalid `satellite_groups.groups` configuration"),
+        };
+        Self {
+            label: config.label,
+            identifier,
+            cache_dir: None,
         }
     }
 }
@@ -125,6 +304,10 @@ enum Identifier {
     CosparId(String),
     /// Group name.
     Group(String),
+    /// NORAD catalog number.
+    NoradId(u64),
+    /// File listing COSPAR IDs or NORAD catalog numbers.
+    File(PathBuf),
 }
 
 impl Display for Identifier {
@@ -132,6 +315,68 @@ impl Display for Identifier {
         match self {
             Identifier::CosparId(id) => write!(f, "{id}"),
             Identifier::Group(group) => write!(f, "{group}"),
+            Identifier::NoradId(id) => write!(f, "{id}"),
+            Identifier::File(path) => {
+                let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                write!(f, "file-{name}")
+            }
         }
     }
 }
+
+/// Returns a group listed in a missing file, so fetching it fails without a
+/// request, cached in its own empty temporary directory, for tests.
+#[cfg(test)]
+pub fn test_group(name: &str) -> Group {
+    let dir = std::env::temp_dir().join(format!("tracker-test-{name}"));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    Group {
+        label: name.into(),
+        identifier: Identifier::File(dir.join("missing.txt")),
+        cache_dir: Some(dir),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::test_object;
+
+    #[tokio::test]
+    async fn corrupted_cache_is_ignored() {
+        let group = test_group("corrupted-cache");
+        let cache_path = group.cache_path();
+        fs::write(&cache_path, "[{\"OBJECT_NAME\":").await.unwrap();
+        assert!(Group::read_cache(&cache_path).await.is_none());
+
+        // The corrupted cache is removed before fetching again
+        assert!(group.get_elements(Duration::MAX).await.is_none());
+        assert!(!cache_path.exists());
+    }
+
+    #[test]
+    fn list_files_with_the_same_name_do_not_share_cache() {
+        let group = |path: &str| Group {
+            label: "Test".into(),
+            identifier: Identifier::File(path.into()),
+            cache_dir: None,
+        };
+        let path = group("a/satellites.txt").cache_path();
+        assert_ne!(path, group("b/satellites.txt").cache_path());
+        assert_eq!(path, group("a/satellites.txt").cache_path());
+        let name = path.file_name().unwrap().to_string_lossy();
+        assert!(name.starts_with("file-satellites-"), "{name}");
+    }
+
+    #[tokio::test]
+    async fn expired_cache_is_used_if_fetching_fails() {
+        let group = test_group("expired-cache");
+        let cache_path = group.cache_path();
+        Group::write_cache(&cache_path, &[test_object(25544).elements().clone()]).await;
+
+        let elements = group.get_elements(Duration::ZERO).await.unwrap();
+        assert_eq!(elements.len(), 1);
+        assert_eq!(elements[0].norad_id, 25544);
+    }
+}
@@ -0,0 +1,323 @@
+use std::sync::LazyLock;
+
+/// Simplified outlines of the continents and major islands, as `(longitude,
+/// latitude)` pairs in degrees.
+#[rustfmt::skip]
+static LAND_POLYGONS: &[&[(f64, f64)]] = &[
+    // North America
+    &[
+        (-168.0, 66.0), (-162.0, 70.0), (-156.0, 71.3), (-141.0, 69.6), (-128.0, 70.0),
+        (-115.0, 68.5), (-98.0, 68.0), (-96.0, 72.0), (-88.0, 68.5), (-82.0, 69.5), (-80.0, 63.0),
+        (-94.0, 60.0), (-93.0, 57.0), (-82.0, 55.0), (-79.0, 51.0), (-77.0, 58.0), (-78.0, 62.0),
+        (-72.0, 61.5), (-65.0, 60.0), (-61.0, 56.0), (-56.0, 52.0), (-60.0, 48.0), (-64.0, 49.0),
+        (-66.0, 44.5), (-70.0, 43.0), (-70.0, 41.5), (-74.0, 40.5), (-76.0, 37.0), (-75.5, 35.2),
+        (-81.0, 31.5), (-80.0, 27.0), (-80.5, 25.2), (-81.8, 26.5), (-83.0, 29.5), (-85.0, 29.8),
+        (-89.0, 30.3), (-90.0, 29.0), (-94.0, 29.6), (-97.2, 27.5), (-97.5, 22.0), (-96.0, 19.0),
+        (-94.5, 18.2), (-91.0, 19.0), (-90.4, 21.0), (-87.0, 21.5), (-88.2, 16.0), (-83.4, 15.0),
+        (-83.7, 11.0), (-81.5, 9.0), (-79.0, 9.4), (-77.4, 8.6), (-78.5, 7.0), (-80.0, 7.3),
+        (-80.5, 8.2), (-83.5, 8.5), (-85.7, 10.0), (-87.5, 13.0), (-91.5, 14.0), (-94.5, 16.2),
+        (-96.5, 15.7), (-101.0, 17.5), (-105.5, 20.5), (-105.2, 23.0), (-109.5, 26.5),
+        (-112.5, 30.0), (-114.7, 31.6), (-112.2, 29.0), (-110.0, 23.0), (-112.0, 24.8),
+        (-115.5, 28.5), (-117.1, 32.5), (-120.6, 34.5), (-123.7, 38.5), (-124.3, 42.0),
+        (-124.0, 46.5), (-124.7, 48.4), (-127.9, 50.8), (-130.5, 54.5), (-135.0, 57.5),
+        (-139.5, 59.8), (-146.0, 60.5), (-152.0, 59.2), (-157.0, 57.5), (-162.0, 55.5),
+        (-158.0, 58.6), (-162.0, 60.0), (-165.5, 62.5), (-163.0, 64.5), (-168.0, 66.0),
+    ],
+    // Greenland
+    &[
+        (-73.0, 78.0), (-66.0, 81.0), (-40.0, 83.5), (-20.0, 82.0), (-18.0, 77.0), (-22.0, 72.0),
+        (-22.0, 70.0), (-32.0, 68.0), (-40.0, 65.0), (-43.0, 60.0), (-48.0, 61.0), (-52.0, 65.0),
+        (-54.0, 69.5), (-58.0, 75.5), (-73.0, 78.0),
+    ],
+    // Baffin Island
+    &[
+        (-80.0, 73.5), (-70.0, 70.0), (-62.0, 66.5), (-65.0, 62.5), (-72.0, 64.0), (-78.0, 64.5),
+        (-74.0, 68.0), (-85.0, 70.0), (-90.0, 73.5), (-80.0, 73.5),
+    ],
+    // Ellesmere Island
+    &[
+        (-90.0, 77.0), (-75.0, 78.5), (-62.0, 82.0), (-75.0, 83.0), (-90.0, 82.0), (-96.0, 79.0),
+        (-90.0, 77.0),
+    ],
+    // Victoria Island
+    &[
+        (-118.0, 69.0), (-105.0, 68.5), (-101.0, 70.0), (-102.0, 73.0), (-117.0, 73.0),
+        (-118.0, 69.0),
+    ],
+    // Newfoundland
+    &[
+        (-59.3, 47.6), (-55.5, 51.6), (-52.7, 47.6), (-55.8, 46.9), (-59.3, 47.6),
+    ],
+    // Cuba
+    &[
+        (-85.0, 21.9), (-81.0, 23.2), (-77.0, 22.3), (-74.2, 20.2), (-77.7, 19.9), (-80.0, 21.7),
+        (-85.0, 21.9),
+    ],
+    // Hispaniola
+    &[
+        (-74.4, 18.6), (-72.7, 19.9), (-69.9, 19.7), (-68.3, 18.6), (-71.0, 18.0), (-74.4, 18.6),
+    ],
+    // South America
+    &[
+        (-77.4, 8.6), (-72.0, 12.0), (-68.0, 10.6), (-62.0, 10.7), (-57.0, 6.0), (-51.0, 4.0),
+        (-50.0, 0.0), (-44.0, -2.5), (-35.0, -5.0), (-35.0, -9.0), (-39.0, -13.5), (-39.0, -17.8),
+        (-41.0, -22.0), (-48.0, -25.5), (-48.5, -28.5), (-53.0, -33.7), (-57.0, -35.0),
+        (-57.0, -37.5), (-62.0, -39.0), (-65.0, -42.0), (-65.0, -45.0), (-67.5, -46.5),
+        (-66.0, -48.0), (-69.0, -51.0), (-68.5, -52.5), (-74.5, -52.0), (-75.5, -47.0),
+        (-74.0, -42.0), (-73.5, -37.0), (-71.7, -30.0), (-70.3, -18.5), (-75.5, -15.0),
+        (-79.5, -7.0), (-81.2, -4.5), (-80.0, -2.0), (-80.2, 1.0), (-78.9, 1.8), (-77.3, 4.0),
+        (-77.9, 7.2), (-77.4, 8.6),
+    ],
+    // Eurasia
+    &[
+        (-5.6, 36.0), (-9.0, 37.0), (-8.8, 42.0), (-9.0, 43.2), (-1.8, 43.4), (-1.2, 46.0),
+        (-4.5, 48.0), (-1.5, 48.7), (1.5, 50.2), (4.0, 51.5), (5.0, 53.2), (8.5, 53.8), (8.5, 57.0),
+        (10.5, 57.7), (10.5, 54.5), (14.0, 54.0), (18.5, 54.8), (21.0, 56.0), (24.0, 57.5),
+        (23.5, 59.3), (30.0, 60.0), (23.0, 60.0), (21.5, 61.5), (21.0, 64.5), (25.5, 65.5),
+        (22.0, 65.8), (17.5, 62.5), (19.0, 60.0), (16.0, 56.5), (12.5, 56.0), (11.0, 59.0),
+        (8.0, 58.0), (5.0, 59.5), (5.0, 62.0), (10.0, 64.0), (14.0, 67.5), (17.0, 69.5),
+        (24.0, 71.0), (28.0, 71.0), (32.0, 69.7), (41.0, 67.5), (33.0, 66.0), (35.0, 64.0),
+        (40.0, 64.5), (44.0, 66.5), (44.0, 68.5), (53.0, 68.5), (58.0, 68.5), (61.0, 69.8),
+        (68.0, 69.0), (67.0, 71.0), (70.0, 73.0), (73.0, 72.5), (74.0, 67.5), (78.0, 72.3),
+        (80.0, 73.5), (87.0, 75.0), (95.0, 76.0), (100.0, 77.0), (105.0, 77.7), (110.0, 76.6),
+        (113.0, 75.6), (113.0, 73.5), (120.0, 73.0), (128.0, 72.5), (130.0, 71.0), (140.0, 72.5),
+        (150.0, 71.5), (160.0, 69.5), (170.0, 70.0), (176.0, 69.7), (180.0, 69.0), (180.0, 65.0),
+        (178.0, 64.5), (177.0, 62.5), (170.0, 60.0), (163.0, 59.8), (162.0, 58.0), (163.0, 56.0),
+        (156.0, 51.0), (156.0, 57.0), (160.0, 61.5), (155.0, 59.3), (143.0, 59.3), (137.0, 54.0),
+        (141.0, 53.0), (140.0, 48.0), (135.0, 43.5), (131.0, 42.5), (129.5, 40.5), (129.5, 35.5),
+        (126.5, 34.5), (126.0, 37.0), (125.0, 39.5), (121.5, 39.0), (122.0, 40.5), (119.0, 39.0),
+        (118.0, 39.2), (119.5, 37.0), (122.5, 37.4), (120.5, 36.0), (119.0, 35.0), (121.9, 31.5),
+        (121.5, 30.0), (122.0, 29.0), (119.5, 25.5), (116.0, 22.8), (113.5, 22.2), (110.0, 21.0),
+        (108.0, 21.5), (106.0, 20.0), (106.5, 17.5), (109.2, 12.0), (107.0, 10.5), (105.0, 8.6),
+        (104.8, 10.2), (103.0, 11.0), (101.0, 12.7), (100.0, 13.5), (99.2, 10.0), (100.3, 8.3),
+        (101.0, 6.8), (103.4, 4.5), (104.2, 1.3), (103.5, 1.3), (101.3, 2.8), (100.3, 5.0),
+        (98.3, 8.0), (98.5, 10.0), (97.8, 16.5), (94.5, 16.0), (94.0, 19.0), (92.3, 21.5),
+        (90.0, 22.0), (86.9, 21.3), (85.0, 19.5), (82.2, 16.6), (80.3, 15.0), (80.1, 13.0),
+        (79.8, 10.3), (77.5, 8.0), (76.6, 8.9), (74.8, 12.8), (73.5, 16.0), (72.8, 19.0),
+        (72.6, 21.4), (70.0, 22.5), (68.8, 23.5), (66.7, 25.4), (61.5, 25.0), (57.3, 25.7),
+        (56.4, 27.1), (54.5, 26.6), (51.5, 27.9), (50.1, 30.2), (47.9, 29.0), (50.8, 24.7),
+        (51.3, 26.1), (51.6, 24.2), (54.0, 24.1), (56.4, 26.4), (56.2, 24.9), (58.7, 23.6),
+        (59.8, 22.3), (57.8, 18.9), (55.3, 17.6), (52.2, 15.7), (45.0, 12.8), (43.3, 12.7),
+        (42.7, 16.0), (40.9, 19.5), (39.0, 21.8), (38.4, 24.0), (36.6, 25.8), (35.0, 28.0),
+        (34.6, 28.1), (34.2, 31.2), (35.0, 32.8), (35.9, 35.5), (36.0, 36.7), (32.5, 36.1),
+        (30.5, 36.5), (27.3, 37.0), (26.3, 39.4), (26.6, 40.4), (29.0, 41.0), (28.0, 41.2),
+        (26.0, 40.8), (23.0, 40.3), (24.0, 38.0), (22.5, 36.5), (21.1, 37.8), (20.0, 39.6),
+        (19.5, 41.8), (16.0, 43.5), (13.7, 45.6), (12.3, 45.3), (12.4, 44.2), (14.0, 42.5),
+        (16.0, 41.4), (18.5, 40.2), (16.5, 38.8), (15.6, 38.2), (16.0, 39.5), (15.6, 40.1),
+        (13.0, 41.2), (11.0, 42.5), (10.0, 44.0), (8.5, 44.3), (6.5, 43.1), (3.2, 43.2),
+        (3.2, 41.9), (0.5, 40.6), (-0.3, 39.5), (0.2, 38.7), (-0.7, 37.6), (-2.1, 36.7),
+        (-4.4, 36.7), (-5.6, 36.0),
+    ],
+    // Great Britain
+    &[
+        (-5.7, 50.0), (1.4, 51.2), (1.7, 52.7), (0.0, 53.5), (-1.5, 55.0), (-2.0, 56.0),
+        (-1.8, 57.6), (-3.0, 58.6), (-5.0, 58.6), (-6.2, 56.8), (-5.0, 55.0), (-3.0, 54.0),
+        (-4.7, 52.8), (-5.2, 51.7), (-3.0, 51.3), (-5.7, 50.0),
+    ],
+    // Ireland
+    &[
+        (-6.0, 52.2), (-6.2, 53.8), (-5.7, 54.7), (-7.3, 55.3), (-10.0, 54.2), (-10.0, 53.4),
+        (-10.3, 51.9), (-9.5, 51.6), (-8.0, 51.8), (-6.0, 52.2),
+    ],
+    // Iceland
+    &[
+        (-24.0, 65.5), (-22.0, 66.4), (-16.0, 66.5), (-13.5, 65.2), (-15.0, 64.3), (-18.7, 63.4),
+        (-22.7, 63.8), (-24.0, 65.5),
+    ],
+    // Svalbard
+    &[
+        (11.0, 78.5), (16.0, 80.0), (27.0, 80.2), (22.0, 78.0), (17.0, 76.6), (11.0, 78.5),
+    ],
+    // Novaya Zemlya
+    &[
+        (52.0, 71.0), (57.5, 75.8), (68.5, 76.9), (68.0, 76.0), (57.0, 70.7), (52.0, 71.0),
+    ],
+    // Sicily
+    &[
+        (12.4, 38.1), (15.6, 38.3), (15.1, 36.7), (12.4, 37.7), (12.4, 38.1),
+    ],
+    // Africa
+    &[
+        (-5.9, 35.8), (-2.0, 35.1), (3.0, 36.8), (10.0, 37.3), (11.0, 35.5), (10.0, 34.0),
+        (11.5, 33.0), (15.5, 32.0), (19.5, 30.5), (20.0, 32.0), (23.0, 32.6), (25.0, 31.7),
+        (29.0, 30.9), (32.3, 31.3), (34.2, 31.2), (34.9, 29.5), (34.0, 27.0), (35.5, 24.0),
+        (37.2, 21.0), (38.5, 18.0), (39.7, 15.4), (41.7, 13.5), (43.3, 12.5), (44.5, 10.4),
+        (51.0, 11.8), (51.1, 10.5), (48.0, 4.3), (43.5, -0.5), (40.3, -2.8), (39.2, -6.5),
+        (39.5, -10.0), (40.5, -15.5), (35.0, -19.5), (35.5, -24.0), (32.8, -26.0), (32.4, -28.6),
+        (30.0, -31.3), (27.0, -33.7), (22.5, -34.0), (18.5, -34.2), (18.2, -32.0), (15.2, -27.0),
+        (14.5, -22.5), (11.8, -17.3), (13.5, -11.5), (12.0, -5.0), (9.5, -1.0), (9.5, 4.0),
+        (8.5, 4.6), (6.0, 4.3), (4.5, 6.3), (1.5, 6.2), (-2.0, 4.8), (-7.5, 4.4), (-11.5, 6.9),
+        (-13.3, 8.5), (-15.3, 11.0), (-17.2, 14.7), (-16.1, 18.5), (-16.5, 19.6), (-17.1, 21.0),
+        (-14.8, 25.5), (-13.0, 27.8), (-9.8, 29.9), (-9.6, 32.5), (-6.8, 34.0), (-5.9, 35.8),
+    ],
+    // Madagascar
+    &[
+        (49.3, -12.0), (50.5, -15.5), (47.2, -25.0), (45.2, -25.5), (43.7, -23.5), (44.3, -20.0),
+        (44.0, -17.0), (47.5, -14.8), (49.3, -12.0),
+    ],
+    // Sri Lanka
+    &[
+        (79.8, 9.8), (81.8, 7.5), (81.3, 6.2), (80.1, 6.0), (79.8, 8.0), (79.8, 9.8),
+    ],
+    // Honshu
+    &[
+        (130.9, 34.0), (132.5, 35.5), (136.0, 35.7), (137.0, 37.0), (140.0, 40.5), (140.0, 41.3),
+        (141.5, 40.5), (142.0, 39.0), (140.9, 36.8), (140.8, 35.2), (139.0, 34.7), (136.8, 34.4),
+        (135.0, 33.7), (132.0, 33.7), (130.9, 34.0),
+    ],
+    // Kyushu
+    &[
+        (129.7, 33.6), (131.5, 33.5), (131.5, 31.5), (130.2, 31.2), (129.7, 33.6),
+    ],
+    // Hokkaido
+    &[
+        (140.0, 41.5), (141.5, 45.4), (144.0, 44.0), (145.5, 43.3), (143.3, 42.0), (141.0, 42.4),
+        (140.0, 41.5),
+    ],
+    // Sakhalin
+    &[
+        (142.0, 46.0), (143.5, 49.5), (144.7, 49.0), (142.7, 54.3), (142.0, 51.0), (141.8, 46.6),
+        (142.0, 46.0),
+    ],
+    // Taiwan
+    &[
+        (120.1, 23.0), (121.0, 25.3), (122.0, 25.0), (120.8, 21.9), (120.1, 23.0),
+    ],
+    // Luzon
+    &[
+        (120.5, 18.5), (122.3, 18.5), (122.0, 16.0), (124.0, 13.0), (121.5, 13.8), (119.8, 16.0),
+        (120.5, 18.5),
+    ],
+    // Mindanao
+    &[
+        (122.0, 7.0), (126.5, 7.0), (126.5, 9.5), (125.2, 9.8), (122.0, 8.0), (122.0, 7.0),
+    ],
+    // Borneo
+    &[
+        (109.0, 1.5), (110.0, 2.0), (111.5, 2.8), (113.0, 3.2), (115.5, 5.5), (117.0, 7.0),
+        (119.0, 5.2), (118.0, 4.5), (118.5, 1.0), (117.5, 0.0), (116.5, -2.0), (116.0, -3.8),
+        (114.5, -4.0), (111.0, -3.0), (110.0, -1.5), (109.0, 1.5),
+    ],
+    // Sumatra
+    &[
+        (95.3, 5.6), (98.0, 4.0), (100.5, 2.0), (104.0, -1.0), (106.0, -3.0), (105.8, -5.8),
+        (104.5, -5.9), (102.0, -4.0), (100.0, -1.0), (98.5, 1.5), (95.3, 5.6),
+    ],
+    // Java
+    &[
+        (105.2, -6.8), (108.0, -6.3), (111.0, -6.5), (114.5, -7.7), (114.4, -8.7), (108.5, -7.8),
+        (105.5, -7.0), (105.2, -6.8),
+    ],
+    // Sulawesi
+    &[
+        (119.0, -5.5), (120.5, -5.5), (120.3, -3.0), (121.0, -2.5), (122.0, -4.8), (123.2, -4.5),
+        (121.5, -1.8), (123.0, -0.9), (125.0, 1.5), (120.8, 1.3), (120.0, 0.0), (119.0, -3.0),
+        (119.0, -5.5),
+    ],
+    // New Guinea
+    &[
+        (131.0, -1.5), (134.0, -0.8), (138.0, -1.5), (141.0, -2.6), (145.8, -5.0), (147.5, -6.0),
+        (147.8, -8.0), (150.0, -10.5), (147.0, -10.2), (144.0, -7.6), (141.0, -9.1), (139.0, -8.0),
+        (138.0, -5.5), (135.0, -4.5), (132.5, -4.0), (131.0, -1.5),
+    ],
+    // Australia
+    &[
+        (113.5, -22.0), (114.0, -26.0), (115.0, -34.0), (118.0, -35.0), (123.5, -34.0),
+        (126.0, -32.3), (131.0, -31.5), (135.0, -34.5), (138.0, -35.5), (139.5, -37.5),
+        (143.5, -38.8), (146.5, -39.0), (150.0, -37.5), (153.5, -28.0), (153.0, -25.0),
+        (150.5, -22.5), (146.0, -18.5), (145.3, -15.0), (143.5, -14.0), (142.5, -10.8),
+        (141.5, -13.0), (141.6, -17.0), (140.0, -17.7), (136.0, -15.8), (135.5, -12.0),
+        (132.0, -11.3), (130.0, -13.0), (127.2, -14.0), (125.5, -14.5), (122.0, -17.0),
+        (121.0, -19.6), (117.0, -20.6), (113.5, -22.0),
+    ],
+    // Tasmania
+    &[
+        (144.7, -40.7), (148.3, -40.9), (148.0, -43.2), (146.5, -43.5), (145.0, -42.2),
+        (144.7, -40.7),
+    ],
+    // North Island
+    &[
+        (172.7, -34.4), (174.5, -36.0), (176.0, -37.5), (178.5, -37.7), (177.0, -39.3),
+        (176.0, -41.3), (174.8, -41.3), (174.5, -39.5), (173.8, -39.2), (174.5, -37.0),
+        (172.7, -34.4),
+    ],
+    // South Island
+    &[
+        (172.5, -40.5), (174.0, -41.3), (173.0, -43.5), (171.0, -45.0), (169.0, -46.6),
+        (166.5, -46.0), (168.0, -44.0), (171.0, -42.0), (172.5, -40.5),
+    ],
+    // Antarctica
+    &[
+        (-180.0, -78.0), (-150.0, -77.0), (-120.0, -74.0), (-100.0, -73.0), (-75.0, -73.0),
+        (-68.0, -70.0), (-63.0, -65.0), (-57.0, -63.3), (-60.0, -64.5), (-62.0, -67.0),
+        (-61.0, -73.0), (-60.0, -75.0), (-40.0, -78.0), (-30.0, -76.0), (-20.0, -73.0),
+        (-10.0, -71.0), (0.0, -70.0), (20.0, -70.0), (40.0, -69.0), (60.0, -67.0), (80.0, -67.0),
+        (100.0, -66.0), (120.0, -66.0), (140.0, -66.5), (160.0, -70.0), (170.0, -72.0),
+        (166.0, -78.0), (180.0, -78.0), (180.0, -90.0), (-180.0, -90.0), (-180.0, -78.0),
+    ],
+];
+
+/// Simplified outlines of the major inland seas, as `(longitude, latitude)`
+/// pairs in degrees.
+#[rustfmt::skip]
+static WATER_POLYGONS: &[&[(f64, f64)]] = &[
+    // Black Sea
+    &[
+        (28.0, 41.2), (28.0, 43.5), (29.7, 45.3), (31.2, 46.6), (33.5, 46.0), (32.5, 45.4),
+        (33.5, 44.5), (36.5, 45.2), (38.0, 47.0), (39.6, 47.0), (38.2, 46.3), (37.5, 44.7),
+        (39.5, 43.5), (41.6, 41.6), (40.0, 41.0), (36.0, 41.7), (33.0, 42.0), (31.0, 41.1),
+        (28.0, 41.2),
+    ],
+    // Caspian Sea
+    &[
+        (49.0, 46.5), (53.0, 47.0), (53.5, 45.5), (51.0, 44.5), (51.3, 43.0), (52.8, 42.0),
+        (53.0, 40.0), (54.0, 37.4), (50.0, 37.0), (49.0, 38.5), (49.5, 40.3), (47.5, 42.0),
+        (47.5, 43.0), (47.0, 44.5), (49.0, 46.5),
+    ],
+];
+
+/// Land mask with a resolution of 1 degree, indexed by `[lat_index][lon_index]`
+/// starting from the north-west corner.
+static LAND_MASK: LazyLock<Vec<[bool; 360]>> = LazyLock::new(|| {
+    (0..180)
+        .map(|lat_index| {
+            let lat = 89.5 - lat_index as f64;
+            std::array::from_fn(|lon_index| {
+                let lon = -179.5 + lon_index as f64;
+                LAND_POLYGONS
+                    .iter()
+                    .any(|polygon| contains(polygon, lon, lat))
+                    && !WATER_POLYGONS
+                        .iter()
+                        .any(|polygon| contains(polygon, lon, lat))
+            })
+        })
+        .collect()
+});
+
+/// Returns whether the given position is on land.
+///
+/// The land mask is coarse, so positions near coastlines may be misclassified.
+///
+/// # Arguments
+///
+/// * `lon` - Longitude in degrees, in the range [-180, 180].
+/// * `lat` - Latitude in degrees, in the range [-90, 90].
+pub fn is_land(lon: f64, lat: f64) -> bool {
+    let lat_index = ((90.0 - lat) as usize).min(179);
+    let lon_index = ((lon + 180.0) as usize).min(359);
+    LAND_MASK[lat_index][lon_index]
+}
+
+/// Returns whether the polygon contains the point, using the even-odd rule.
+fn contains(polygon: &[(f64, f64)], x: f64, y: f64) -> bool {
+    let mut inside = false;
+    for window in polygon.windows(2) {
+        let (x1, y1) = window[0];
+        let (x2, y2) = window[1];
+        if (y1 > y) != (y2 > y) && x < x1 + (y - y1) * (x2 - x1) / (y2 - y1) {
+            inside = !inside;
+        }
+    }
+    inside
+}
@@ -0,0 +1,42 @@
+use std::{
+    collections::VecDeque,
+    sync::{LazyLock, Mutex},
+};
+
+use chrono::{DateTime, Local};
+
+/// The maximum number of log entries kept.
+const CAPACITY: usize = 200;
+
+/// Recent log entries, ordered from oldest to newest.
+static LOG: LazyLock<Mutex<VecDeque<Entry>>> =
+    LazyLock::new(|| Mutex::new(VecDeque::with_capacity(CAPACITY)));
+
+/// A log entry.
+#[derive(Clone)]
+pub struct Entry {
+    /// The local time the entry was logged.
+    pub time: DateTime<Local>,
+    pub message: String,
+}
+
+/// Appends a message to the log, discarding the oldest entry if full.
+pub fn push(message: impl Into<String>) {
+    let mut log = LOG.lock().unwrap();
+    if log.len() == CAPACITY {
+        log.pop_front();
+    }
+    log.push_back(Entry {
+        time: Local::now(),
+        message: message.into(),
+    });
+}
+
+/// Returns the last `count` entries, ordered from oldest to newest.
+pub fn last(count: usize) -> Vec<Entry> {
+    let log = LOG.lock().unwrap();
+    log.iter()
+        .skip(log.len().saturating_sub(count))
+        .cloned()
+        .collect()
+}
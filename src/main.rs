@@ -8,9 +8,19 @@ mod app;
 mod config;
 mod coordinates;
 mod event;
+mod format;
+mod frame_dump;
+mod geojson;
+mod gpsd;
 mod group;
+mod land;
+mod log;
 mod object;
+mod preset;
+mod rotator;
+mod session;
 mod shared_state;
+mod tle;
 mod tui;
 mod utils;
 mod widgets;
@@ -31,7 +41,7 @@ async fn main() -> Result<()> {
     let locale = sys_locale::get_locale().unwrap_or_else(|| String::from("en-US"));
     rust_i18n::set_locale(&locale);
 
-    let config = load_config().unwrap_or_else(|e| {
+    let config = Config::load().unwrap_or_else(|e| {
         eprintln!("Failed to load configuration: {e}");
         eprintln!("Using default configuration.");
         Config::default()
@@ -39,15 +49,3 @@ async fn main() -> Result<()> {
     let mut app = App::with_config(config).context("failed to initialize application")?;
     app.run().await
 }
-
-fn load_config() -> Result<Config> {
-    let path = std::env::home_dir()
-        .context("failed to get home directory")?
-        .join(".config/tracker/config.toml");
-    if !path.exists() {
-        return Ok(Config::default());
-    }
-    let content = std::fs::read_to_string(&path)?;
-    let config: Config = toml::from_str(&content)?;
-    Ok(config)
-}
@@ -1,11 +1,15 @@
 use chrono::{DateTime, Duration, TimeZone, Utc};
 
 use crate::{
-    coordinates::{Lla, Teme},
+    coordinates::{Lla, Teme, wgs84},
     utils::*,
 };
 
 const SECONDS_PER_DAY: f64 = 24.0 * 60.0 * 60.0;
+/// Earth's gravitational parameter in km³/s².
+const EARTH_MU: f64 = 398600.4418;
+/// Earth's second zonal harmonic coefficient.
+const EARTH_J2: f64 = 1.08262668e-3;
 
 /// A satellite object with orbital elements.
 #[derive(Clone, Debug)]
@@ -14,6 +18,8 @@ pub struct Object {
     orbital_period: Duration,
     elements: sgp4::Elements,
     constants: sgp4::Constants,
+    /// The label of the group the object was loaded from, if any.
+    group: Option<String>,
 }
 
 impl Object {
@@ -26,36 +32,232 @@ impl Object {
             orbital_period,
             constants: sgp4::Constants::from_elements(&elements).unwrap(),
             elements,
+            group: None,
         }
     }
 
+    /// Tags the object with the label of the group it was loaded from.
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+
     /// Returns the name of the object.
     pub fn name(&self) -> Option<&str> {
         self.elements.object_name.as_deref()
     }
 
+    /// Returns the label of the group the object was loaded from, if any.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
     /// Returns the UTC timestamp of the elements.
     pub fn epoch(&self) -> DateTime<Utc> {
         self.epoch
     }
 
     /// Returns the orbital period of the object.
+    ///
+    /// This is the anomalistic period (perigee to perigee) derived from the
+    /// mean motion, which is used as the length of the ground track. See
+    /// also [`Object::nodal_period`].
     pub fn orbital_period(&self) -> &Duration {
         &self.orbital_period
     }
 
+    /// Returns the approximate nodal period of the object.
+    ///
+    /// This is the period between two consecutive ascending node crossings,
+    /// which differs from the anomalistic period due to the apsidal precession
+    /// caused by the Earth's oblateness (J2).
+    pub fn nodal_period(&self) -> Duration {
+        let seconds =
+            self.orbital_period.as_seconds_f64() / (1.0 + self.apsidal_precession_ratio());
+        Duration::milliseconds((seconds * 1000.0) as i64)
+    }
+
+    /// Returns the repeat cycle of the ground track, or `None` if the ground
+    /// track does not repeat within a month.
+    ///
+    /// The ground track repeats when a whole number of nodal periods fits in a
+    /// whole number of nodal days, i.e. Earth rotations relative to the
+    /// precessing orbital plane. For sun-synchronous orbits, a nodal day is a
+    /// solar day.
+    pub fn repeat_cycle(&self) -> Option<RepeatCycle> {
+        /// Earth's rotation rate in rad/s.
+        const EARTH_ROTATION_RATE: f64 = 7.292_115e-5;
+        const MAX_DAYS: u32 = 30;
+        /// The maximum drift of the ground track in orbits after a cycle.
+        const MAX_DRIFT: f64 = 0.003;
+
+        let mean_motion = self.elements.mean_motion * std::f64::consts::TAU / SECONDS_PER_DAY; // rad/s
+        let cos_inc = self.elements.inclination.to_radians().cos();
+        let nodal_precession_rate = -1.5 * mean_motion * self.oblateness_factor() * cos_inc;
+        let nodal_day = std::f64::consts::TAU / (EARTH_ROTATION_RATE - nodal_precession_rate);
+        let nodal_mean_motion = mean_motion * (1.0 + self.apsidal_precession_ratio());
+        let orbits_per_day = nodal_day * nodal_mean_motion / std::f64::consts::TAU;
+
+        (1..=MAX_DAYS).find_map(|days| {
+            let orbits = orbits_per_day * days as f64;
+            let whole_orbits = orbits.round();
+            ((orbits - whole_orbits).abs() <= MAX_DRIFT && whole_orbits >= 1.0).then_some(
+                RepeatCycle {
+                    days,
+                    orbits: whole_orbits as u32,
+                },
+            )
+        })
+    }
+
+    /// Returns the ratio of the apsidal precession rate to the mean motion.
+    fn apsidal_precession_ratio(&self) -> f64 {
+        let cos_inc = self.elements.inclination.to_radians().cos();
+        0.75 * self.oblateness_factor() * (5.0 * cos_inc.powi(2) - 1.0)
+    }
+
+    /// Returns the factor `J2 (Re / p)²` scaling the secular perturbations
+    /// caused by the Earth's oblateness, where `p` is the semi-latus rectum.
+    fn oblateness_factor(&self) -> f64 {
+        let mean_motion = self.elements.mean_motion * std::f64::consts::TAU / SECONDS_PER_DAY; // rad/s
+        let semi_major_axis = (EARTH_MU / mean_motion.powi(2)).cbrt();
+        let semi_latus_rectum = semi_major_axis * (1.0 - self.elements.eccentricity.powi(2));
+        EARTH_J2 * (wgs84::A / semi_latus_rectum).powi(2)
+    }
+
+    /// Returns whether the object is in a geosynchronous orbit, i.e. it orbits
+    /// once per sidereal day.
+    pub fn is_geosynchronous(&self) -> bool {
+        /// Mean motion of a geosynchronous orbit in revolutions per day.
+        const GEOSYNCHRONOUS_MEAN_MOTION: f64 = 1.0027;
+        const MAX_MEAN_MOTION_DELTA: f64 = 0.01;
+
+        (self.elements.mean_motion - GEOSYNCHRONOUS_MEAN_MOTION).abs() < MAX_MEAN_MOTION_DELTA
+    }
+
+    /// Returns whether the object is in a near-geostationary orbit, i.e. a
+    /// geosynchronous, near-circular and near-equatorial orbit, so that it
+    /// stays almost still over the ground.
+    pub fn is_geostationary(&self) -> bool {
+        const MAX_INCLINATION_DEG: f64 = 1.0;
+        const MAX_ECCENTRICITY: f64 = 0.01;
+
+        self.is_geosynchronous()
+            && self.elements.inclination < MAX_INCLINATION_DEG
+            && self.elements.eccentricity < MAX_ECCENTRICITY
+    }
+
+    /// Returns the beta angle in degrees at the given time, i.e. the angle
+    /// between the orbital plane and the direction of the Sun.
+    ///
+    /// The angle is positive if the Sun is on the side of the orbital plane
+    /// from which the object is seen moving counterclockwise.
+    pub fn beta_angle(&self, time: &DateTime<Utc>) -> Result<f64, sgp4::Error> {
+        let minutes_since_epoch = (*time - self.epoch).as_seconds_f64() / 60.0;
+        let prediction = self
+            .constants
+            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))?;
+
+        // Normal of the orbital plane, rotated into the ECEF frame of the Sun
+        // direction
+        let [x, y, z] = prediction.position;
+        let [vx, vy, vz] = prediction.velocity;
+        let normal = Teme::new(y * vz - z * vy, z * vx - x * vz, x * vy - y * vx);
+        let gmst = gmst_from_jd_tt(epoch_from_utc(time).to_jde_tt_days());
+        let normal = normal.to_ecef(gmst);
+        let norm = (normal.x.powi(2) + normal.y.powi(2) + normal.z.powi(2)).sqrt();
+
+        let (sub_lon, sub_lat) = subsolar_point(time);
+        let sun = [
+            sub_lat.cos() * sub_lon.cos(),
+            sub_lat.cos() * sub_lon.sin(),
+            sub_lat.sin(),
+        ];
+        let projection = (normal.x * sun[0] + normal.y * sun[1] + normal.z * sun[2]) / norm;
+        Ok(projection.asin().to_degrees())
+    }
+
+    /// Returns whether an orbit with the given beta angle in degrees is
+    /// illuminated by the Sun for the full revolution.
+    ///
+    /// Assumes a circular orbit at the mean altitude and a spherical Earth.
+    pub fn is_full_sun(&self, beta_angle: f64) -> bool {
+        let mean_motion = self.elements.mean_motion * std::f64::consts::TAU / SECONDS_PER_DAY; // rad/s
+        let semi_major_axis = (EARTH_MU / mean_motion.powi(2)).cbrt();
+        let critical_angle = (wgs84::A / semi_major_axis).asin().to_degrees();
+        beta_angle.abs() > critical_angle
+    }
+
+    /// Returns the mean anomaly in degrees at the given time, advanced from the
+    /// epoch by the mean motion.
+    pub fn mean_anomaly(&self, time: &DateTime<Utc>) -> f64 {
+        let days_since_epoch = (*time - self.epoch).as_seconds_f64() / SECONDS_PER_DAY;
+        (self.elements.mean_anomaly + self.elements.mean_motion * days_since_epoch * 360.0)
+            .rem_euclid(360.0)
+    }
+
+    /// Returns the eccentric anomaly in degrees at the given time.
+    pub fn eccentric_anomaly(&self, time: &DateTime<Utc>) -> f64 {
+        solve_kepler(
+            self.mean_anomaly(time).to_radians(),
+            self.elements.eccentricity,
+        )
+        .to_degrees()
+        .rem_euclid(360.0)
+    }
+
+    /// Returns the true anomaly in degrees at the given time.
+    pub fn true_anomaly(&self, time: &DateTime<Utc>) -> f64 {
+        let eccentric_anomaly = self.eccentric_anomaly(time).to_radians();
+        let e = self.elements.eccentricity;
+        let (sin_e, cos_e) = eccentric_anomaly.sin_cos();
+        ((1.0 - e.powi(2)).sqrt() * sin_e)
+            .atan2(cos_e - e)
+            .to_degrees()
+            .rem_euclid(360.0)
+    }
+
     /// Returns the SGP4 elements of the object.
     pub fn elements(&self) -> &sgp4::Elements {
         &self.elements
     }
 
+    /// Returns the direction of travel of the sub-point of the object at the
+    /// given time, in degrees clockwise from north.
+    ///
+    /// Computed from the sub-points at the given time and a few seconds later.
+    pub fn heading(&self, time: &DateTime<Utc>) -> Result<f64, sgp4::Error> {
+        const STEP: Duration = Duration::seconds(5);
+
+        let from = self.predict(time)?;
+        let to = self.predict(&(*time + STEP))?;
+        Ok(initial_bearing(
+            (from.longitude(), from.latitude()),
+            (to.longitude(), to.latitude()),
+        ))
+    }
+
+    /// Returns the speed in km/s at which the sub-point of the object moves
+    /// along the surface of the Earth at the given time.
+    ///
+    /// Unlike the orbital speed, this accounts for the rotation of the Earth,
+    /// and is close to zero for geostationary objects. Computed from the
+    /// sub-points at the given time and a few seconds later.
+    pub fn ground_speed(&self, time: &DateTime<Utc>) -> Result<f64, sgp4::Error> {
+        const STEP: Duration = Duration::seconds(5);
+
+        let from = self.predict(time)?;
+        let to = self.predict(&(*time + STEP))?;
+        let distance = central_angle(
+            (from.longitude(), from.latitude()),
+            (to.longitude(), to.latitude()),
+        ) * wgs84::MEAN_RADIUS;
+        Ok(distance / STEP.as_seconds_f64())
+    }
+
     /// Predicts the state of the object at the given time.
     pub fn predict(&self, time: &DateTime<Utc>) -> Result<State, sgp4::Error> {
-        let minutes_since_epoch = (*time - self.epoch).as_seconds_f64() / 60.0;
-
-        let prediction = self
-            .constants
-            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))?;
+        let prediction = self.propagate(time)?;
 
         let teme = Teme::from(prediction.position);
         let epoch = epoch_from_utc(time);
@@ -67,6 +269,80 @@ impl Object {
             velocity: prediction.velocity.into(),
         })
     }
+
+    /// Predicts the position of the object in the TEME frame, in km, at the
+    /// given time.
+    pub fn predict_teme(&self, time: &DateTime<Utc>) -> Result<Teme, sgp4::Error> {
+        Ok(self.propagate(time)?.position.into())
+    }
+
+    fn propagate(&self, time: &DateTime<Utc>) -> Result<sgp4::Prediction, sgp4::Error> {
+        let minutes_since_epoch = (*time - self.epoch).as_seconds_f64() / 60.0;
+        self.constants
+            .propagate(sgp4::MinutesSinceEpoch(minutes_since_epoch))
+    }
+}
+
+/// Returns an object with the orbital elements of the ISS and the given NORAD
+/// ID, for tests.
+#[cfg(test)]
+pub fn test_object(norad_id: u64) -> Object {
+    let elements = serde_json::from_value(serde_json::json!({
+        "OBJECT_NAME": "ISS (ZARYA)",
+        "OBJECT_ID": "1998-067A",
+        "EPOCH": "2024-01-01T12:00:00.000000",
+        "MEAN_MOTION": 15.49815296,
+        "ECCENTRICITY": 0.0006703,
+        "INCLINATION": 51.6416,
+        "RA_OF_ASC_NODE": 247.4627,
+        "ARG_OF_PERICENTER": 130.536,
+        "MEAN_ANOMALY": 325.0288,
+        "EPHEMERIS_TYPE": 0,
+        "CLASSIFICATION_TYPE": "U",
+        "NORAD_CAT_ID": norad_id,
+        "ELEMENT_SET_NO": 999,
+        "REV_AT_EPOCH": 43293,
+        "BSTAR": 0.00030239,
+        "MEAN_MOTION_DOT": 0.00016717,
+        "MEAN_MOTION_DDOT": 0
+    }))
+    .unwrap();
+    Object::from_elements(elements)
+}
+
+/// Solves Kepler's equation `M = E - e sin E` for the eccentric anomaly `E`
+/// in radians, given the mean anomaly `M` in radians and the eccentricity `e`
+/// of an elliptic orbit.
+///
+/// Uses Newton's method, which converges for all eccentricities below 1 when
+/// starting from `π` for high eccentricities.
+fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> f64 {
+    const MAX_ITERATIONS: usize = 50;
+    const TOLERANCE: f64 = 1e-12;
+
+    let mut eccentric_anomaly = if eccentricity > 0.8 {
+        std::f64::consts::PI
+    } else {
+        mean_anomaly
+    };
+    for _ in 0..MAX_ITERATIONS {
+        let delta = (eccentric_anomaly - eccentricity * eccentric_anomaly.sin() - mean_anomaly)
+            / (1.0 - eccentricity * eccentric_anomaly.cos());
+        eccentric_anomaly -= delta;
+        if delta.abs() < TOLERANCE {
+            break;
+        }
+    }
+    eccentric_anomaly
+}
+
+/// The period after which the ground track of an object repeats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RepeatCycle {
+    /// Number of nodal days in the cycle.
+    pub days: u32,
+    /// Number of orbits in the cycle.
+    pub orbits: u32,
 }
 
 #[derive(Clone, Debug)]
@@ -83,6 +359,16 @@ impl State {
         self.position.lat
     }
 
+    /// Returns the geocentric latitude of the object in degrees, the angle
+    /// between the equatorial plane and the line from the center of the Earth.
+    ///
+    /// Unlike [`State::latitude`], this is not the latitude of the sub-point on
+    /// the WGS84 ellipsoid.
+    pub fn geocentric_latitude(&self) -> f64 {
+        let ecef = self.position.to_ecef();
+        ecef.z.atan2(ecef.x.hypot(ecef.y)).to_degrees()
+    }
+
     /// Returns the longitude of the object in degrees.
     pub fn longitude(&self) -> f64 {
         self.position.lon
@@ -93,8 +379,127 @@ impl State {
         self.position.alt
     }
 
-    /// Returns the speed of the object in km/s.
+    /// Returns whether the object has decayed, i.e. is propagated below the
+    /// surface of the Earth.
+    pub fn is_decayed(&self) -> bool {
+        self.altitude() < 0.0
+    }
+
+    /// Returns the topocentric right ascension and declination in degrees of
+    /// the object as seen from the observer at the given time.
+    ///
+    /// The line of sight is rotated from the ECEF frame to the equator and
+    /// equinox of date by the Greenwich Mean Sidereal Time, so the result can
+    /// be matched against a star chart.
+    pub fn ra_dec(&self, observer: &Lla, time: &DateTime<Utc>) -> (f64, f64) {
+        let observer = observer.to_ecef();
+        let target = self.position.to_ecef();
+        let (dx, dy, dz) = (
+            target.x - observer.x,
+            target.y - observer.y,
+            target.z - observer.z,
+        );
+
+        let gmst = gmst_from_jd_tt(epoch_from_utc(time).to_jde_tt_days());
+        let (sin_theta, cos_theta) = gmst.sin_cos();
+        let x = cos_theta * dx - sin_theta * dy;
+        let y = sin_theta * dx + cos_theta * dy;
+
+        let ra = y.atan2(x).to_degrees().rem_euclid(360.0);
+        let dec = dz.atan2(x.hypot(y)).to_degrees();
+        (ra, dec)
+    }
+
+    /// Returns the orbital speed of the object in km/s, relative to the
+    /// inertial frame.
     pub fn speed(&self) -> f64 {
         (self.velocity.x.powi(2) + self.velocity.y.powi(2) + self.velocity.z.powi(2)).sqrt()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kepler_solution_satisfies_equation() {
+        use std::f64::consts::PI;
+
+        for eccentricity in [0.0, 0.1, 0.5, 0.9, 0.99] {
+            for mean_anomaly in [
+                0.0,
+                1e-6,
+                0.01,
+                1.0,
+                PI - 0.01,
+                PI,
+                PI + 0.01,
+                2.0 * PI - 1e-6,
+            ] {
+                let eccentric_anomaly = solve_kepler(mean_anomaly, eccentricity);
+                let residual =
+                    eccentric_anomaly - eccentricity * eccentric_anomaly.sin() - mean_anomaly;
+                assert!(
+                    residual.abs() < 1e-9,
+                    "e = {eccentricity}, M = {mean_anomaly}: residual {residual}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn ra_dec_matches_az_el() {
+        let object = test_object(25544);
+        let observer = Lla::new(45.0, 10.0, 0.2);
+        let time = object.epoch();
+        let state = object.predict(&time).unwrap();
+        let (az, el) = state.position.az_el(&observer);
+        let (ra, dec) = state.ra_dec(&observer, &time);
+
+        // Convert back to the horizontal frame through the local hour angle
+        let gmst = gmst_from_jd_tt(epoch_from_utc(&time).to_jde_tt_days());
+        let hour_angle = gmst + observer.lon.to_radians() - ra.to_radians();
+        let (lat, dec) = (observer.lat.to_radians(), dec.to_radians());
+        let expected_el = (lat.sin() * dec.sin() + lat.cos() * dec.cos() * hour_angle.cos()).asin();
+        let expected_az = (-hour_angle.sin() * dec.cos())
+            .atan2(lat.cos() * dec.sin() - lat.sin() * dec.cos() * hour_angle.cos());
+
+        assert!((el - expected_el.to_degrees()).abs() < 1e-6);
+        let az_error = (az - expected_az.to_degrees()).rem_euclid(360.0);
+        assert!(az_error.min(360.0 - az_error) < 1e-6);
+    }
+
+    #[test]
+    fn ground_speed_is_slower_than_orbital_speed() {
+        let object = test_object(25544);
+        let time = object.epoch();
+        let orbital_speed = object.predict(&time).unwrap().speed();
+        let ground_speed = object.ground_speed(&time).unwrap();
+
+        // The sub-point of the ISS moves about 7% slower at its altitude,
+        // give or take the rotation of the Earth
+        assert!((7.6..7.7).contains(&orbital_speed));
+        assert!((6.7..7.5).contains(&ground_speed));
+    }
+
+    #[test]
+    fn repeat_cycle_is_found_for_sun_synchronous_orbits() {
+        // Landsat 8 repeats its ground track every 233 orbits in 16 days
+        let mut elements = test_object(39084).elements().clone();
+        elements.mean_motion = 14.5711;
+        elements.eccentricity = 0.0001;
+        elements.inclination = 98.2;
+        let landsat = Object::from_elements(elements);
+        assert_eq!(
+            landsat.repeat_cycle(),
+            Some(RepeatCycle {
+                days: 16,
+                orbits: 233
+            })
+        );
+
+        let mut elements = test_object(1).elements().clone();
+        elements.mean_motion = 14.5735;
+        assert_eq!(Object::from_elements(elements).repeat_cycle(), None);
+    }
+}
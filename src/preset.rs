@@ -0,0 +1,149 @@
+use chrono::Duration;
+use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    app::States,
+    log,
+    session::Session,
+    widgets::{tabs::Tab, world_map::MapView},
+};
+
+/// A named snapshot of the view state, saved in the session.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    /// Name of the preset, displayed when it is recalled.
+    pub name: String,
+    /// Labels of the selected satellite groups, excluding the favorites.
+    pub groups: Vec<String>,
+    /// NORAD ID of the selected object.
+    pub selected_object: Option<u64>,
+    /// View of the world map.
+    pub map: MapView,
+    /// The selected tab.
+    pub tab: Tab,
+    /// Offset of the simulation time from the current time in seconds.
+    pub time_offset_secs: i64,
+    /// Whether the simulation time is frozen.
+    pub frozen: bool,
+}
+
+impl Preset {
+    /// Captures the current view state as a preset with the given name.
+    fn capture(name: String, states: &States) -> Self {
+        Self {
+            name,
+            groups: states.satellite_groups_state.selected_groups(),
+            selected_object: states
+                .shared
+                .selected_object
+                .as_ref()
+                .map(|object| object.elements().norad_id),
+            map: states.world_map_state.view(),
+            tab: states.tab_state.selected,
+            time_offset_secs: states.shared.time.time_offset().num_seconds(),
+            frozen: states.shared.time.is_frozen(),
+        }
+    }
+
+    /// Restores the view state of the preset.
+    ///
+    /// Returns the labels of the groups that are no longer configured.
+    fn apply(&self, states: &mut States) -> Vec<String> {
+        let shared = &mut states.shared;
+        shared.selected_object = None;
+        shared.objects.clear();
+        let missing_groups = states.satellite_groups_state.select_groups(&self.groups);
+        if let Some(norad_id) = self.selected_object {
+            states.satellite_groups_state.select_when_loaded(norad_id);
+        }
+
+        states.world_map_state.set_view(self.map.clone());
+        states.tab_state.select(self.tab);
+
+        if self.frozen != shared.time.is_frozen() {
+            shared.time.toggle_frozen();
+        }
+        shared
+            .time
+            .set_time_offset(Duration::seconds(self.time_offset_secs));
+        missing_groups
+    }
+}
+
+/// Saves the current view state as the preset in the given slot, keeping the
+/// name of the preset it replaces.
+pub fn save(slot: u8, states: &mut States) {
+    let key = slot.to_string();
+    let name = Session::load()
+        .presets
+        .get(&key)
+        .map(|preset| preset.name.clone())
+        .unwrap_or_else(|| t!("preset-default_name", slot = slot).into_owned());
+    let preset = Preset::capture(name.clone(), states);
+
+    match Session::update(|session| {
+        session.presets.insert(key, preset);
+    }) {
+        Ok(()) => states
+            .toast_state
+            .show(t!("toast-preset_saved", name = name)),
+        Err(e) => {
+            log::push(format!("Failed to save session: {e}"));
+            states.toast_state.show(t!("toast-preset_save_failed"));
+        }
+    }
+}
+
+/// Recalls the preset in the given slot.
+pub fn recall(slot: u8, states: &mut States) {
+    let Some(preset) = Session::load().presets.remove(&slot.to_string()) else {
+        states
+            .toast_state
+            .show(t!("toast-preset_empty", slot = slot));
+        return;
+    };
+
+    let missing_groups = preset.apply(states);
+    if missing_groups.is_empty() {
+        states
+            .toast_state
+            .show(t!("toast-preset_recalled", name = preset.name));
+    } else {
+        let groups = missing_groups.join(", ");
+        log::push(format!(
+            "Preset {} refers to unknown groups: {groups}",
+            preset.name
+        ));
+        states.toast_state.show(t!(
+            "toast-preset_missing_groups",
+            name = preset.name,
+            groups = groups
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_presets_are_loaded() {
+        let session: Session = toml::from_str(
+            r#"
+            [presets.1]
+            name = "ISS over Europe"
+            groups = ["Space Stations"]
+            selected_object = 25544
+            tab = "sky"
+            "#,
+        )
+        .unwrap();
+        let preset = &session.presets["1"];
+        assert_eq!(preset.groups, ["Space Stations"]);
+        assert_eq!(preset.selected_object, Some(25544));
+        assert!(preset.tab == Tab::Sky);
+        assert!(!preset.map.follow_object);
+    }
+}
@@ -0,0 +1,108 @@
+use std::time::{Duration, Instant};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::mpsc,
+};
+
+use crate::{config::RotatorConfig, shared_state::SharedState};
+
+/// The interval between two position commands.
+const UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+/// The delay before reconnecting to rotctld after the connection is lost.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+/// A controller pointing an antenna rotator at the selected object through
+/// rotctld.
+///
+/// See <https://hamlib.sourceforge.net/html/rotctld.1.html>.
+pub struct Rotator {
+    /// Sender for the azimuth and elevation to point the rotator at.
+    sender: mpsc::UnboundedSender<(f64, f64)>,
+    /// Minimum elevation in degrees below which the rotator is not moved.
+    min_elevation_deg: f64,
+    /// The instant the last position was sent.
+    last_update: Option<Instant>,
+}
+
+impl Rotator {
+    /// Creates a new `Rotator` with the given configuration, or `None` if it
+    /// is disabled.
+    pub fn with_config(config: RotatorConfig) -> Option<Self> {
+        config.enabled.then(|| Self {
+            sender: spawn_controller(format!("{}:{}", config.host, config.port)),
+            min_elevation_deg: config.min_elevation_deg,
+            last_update: None,
+        })
+    }
+
+    /// Sends the azimuth and elevation of the selected object as seen from
+    /// the ground station, at most once per second and only while the object
+    /// is above the minimum elevation.
+    pub fn update(&mut self, shared: &SharedState) {
+        if self
+            .last_update
+            .is_some_and(|instant| instant.elapsed() < UPDATE_INTERVAL)
+        {
+            return;
+        }
+        let (Some(object), Some(station)) = (&shared.selected_object, &shared.ground_station)
+        else {
+            return;
+        };
+
+        let state = object.predict(&shared.time.time()).unwrap();
+        let (az, el) = state.position.az_el(&station.position);
+        if el < self.min_elevation_deg {
+            return;
+        }
+        let _ = self.sender.send((az, el));
+        self.last_update = Some(Instant::now());
+    }
+}
+
+/// Spawns an async task that sends the received azimuth and elevation pairs
+/// to rotctld.
+///
+/// The task reconnects if the connection is lost, and stops once the returned
+/// sender is dropped.
+///
+/// # Arguments
+///
+/// * `address` - The address of the rotctld daemon, e.g. `127.0.0.1:4533`.
+fn spawn_controller(address: String) -> mpsc::UnboundedSender<(f64, f64)> {
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while !rx.is_closed() {
+            let _ = send_positions(&address, &mut rx).await;
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    });
+    tx
+}
+
+/// Connects to rotctld and sends the received positions until the connection
+/// is closed.
+async fn send_positions(
+    address: &str,
+    rx: &mut mpsc::UnboundedReceiver<(f64, f64)>,
+) -> std::io::Result<()> {
+    let (reader, mut writer) = TcpStream::connect(address).await?.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    while let Some(mut position) = rx.recv().await {
+        // Skip the positions that became stale while waiting
+        while let Ok(latest) = rx.try_recv() {
+            position = latest;
+        }
+        let (az, el) = position;
+        writer
+            .write_all(format!("P {az:.2} {el:.2}\n").as_bytes())
+            .await?;
+        // Wait for the `RPRT` reply before sending the next command
+        if lines.next_line().await?.is_none() {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::{Context as _, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{log, preset::Preset};
+
+/// State persisted across runs, such as the favorite objects and the view
+/// presets.
+///
+/// Unlike the configuration, the session is written by the application.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// NORAD IDs of the favorite objects.
+    pub favorites: Vec<u64>,
+    /// View presets indexed by their slot number.
+    pub presets: BTreeMap<String, Preset>,
+}
+
+impl Session {
+    /// Loads the session, or returns an empty session if it does not exist or
+    /// cannot be read.
+    pub fn load() -> Self {
+        let Some(path) = Self::path() else {
+            return Self::default();
+        };
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        toml::from_str(&content).unwrap_or_else(|e| {
+            log::push(format!("Failed to parse session: {e}"));
+            Self::default()
+        })
+    }
+
+    /// Saves the session.
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().context("failed to get home directory")?;
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        std::fs::write(&path, toml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Updates the session with `update` and saves it, keeping the other
+    /// fields as stored.
+    pub fn update(update: impl FnOnce(&mut Self)) -> Result<()> {
+        let mut session = Self::load();
+        update(&mut session);
+        session.save()
+    }
+
+    fn path() -> Option<PathBuf> {
+        Some(std::env::home_dir()?.join(".config/tracker/session.toml"))
+    }
+}
@@ -1,6 +1,14 @@
-use chrono::{DateTime, Duration, Utc};
+use std::fmt::Display;
 
-use crate::{config::SkyConfig, coordinates::Lla, object::Object};
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use ratatui::{style::Modifier, symbols::Marker};
+
+use crate::{
+    config::{AppConfig, SkyConfig},
+    coordinates::Lla,
+    object::Object,
+    utils::{Pass, calculate_pass_times},
+};
 
 /// Shared state accessible by all widgets.
 #[derive(Default)]
@@ -9,66 +17,327 @@ pub struct SharedState {
     pub objects: Vec<Object>,
     /// Selected satellite object.
     pub selected_object: Option<Object>,
+    /// Reference satellite object compared with the selected object.
+    pub reference_object: Option<Object>,
+    /// NORAD IDs of the objects selected with a selection rectangle, which
+    /// unlike indices survive the refreshes of the objects.
+    pub multi_selection: Vec<u64>,
     /// Hovered satellite object.
     pub hovered_object: Option<Object>,
     /// Simulation time state.
     pub time: TimeState,
+    /// Offset from the simulation time of the time previewed on the
+    /// timeline, if any.
+    pub preview_offset: Option<Duration>,
+    /// NORAD IDs of the favorite objects.
+    pub favorites: Vec<u64>,
     /// Configured ground station.
     pub ground_station: Option<Station>,
+    /// Format string used to display timestamps.
+    time_format: String,
+    /// Format string used to display the time of day, derived from the time
+    /// format.
+    time_of_day_format: String,
+    /// Whether to use a non-blinking emphasis instead of blinking text.
+    pub disable_blink: bool,
+    /// Marker used to draw the canvases.
+    pub marker: Marker,
+    /// Predicted passes of the selected object over the ground station.
+    pub pass_cache: PassCache,
 }
 
 impl SharedState {
-    pub fn with_config(config: SkyConfig) -> Self {
-        let ground_station = config.ground_station.map(|station| Station {
-            name: station
-                .name
-                .unwrap_or_else(|| station.position.country_city().1),
-            position: station.position,
-        });
+    /// Creates a new `SharedState` with the given configuration.
+    ///
+    /// The ground station position is read from the `TRACKER_STATION_LAT`,
+    /// `TRACKER_STATION_LON` and `TRACKER_STATION_ALT` environment variables
+    /// if set, otherwise from the configuration.
+    pub fn with_config(app_config: AppConfig, config: SkyConfig, favorites: Vec<u64>) -> Self {
+        let name = config
+            .ground_station
+            .as_ref()
+            .and_then(|station| station.name.clone());
+        let ground_station = position_from_env()
+            .or(config.ground_station.map(|station| station.position))
+            .map(|position| Station::new(name, position));
 
         Self {
+            favorites,
             ground_station,
+            time_of_day_format: time_of_day_format(&app_config.time_format),
+            time_format: app_config.time_format,
+            disable_blink: app_config.disable_blink,
+            marker: app_config.marker.into(),
+            pass_cache: PassCache::new(
+                Duration::hours(config.pass_look_ahead_hours),
+                config.min_pass_elevation_deg,
+            ),
             ..Self::default()
         }
     }
+
+    /// Updates the pass cache for the selected object and ground station.
+    pub fn update_pass_cache(&mut self) {
+        self.pass_cache.update(
+            self.selected_object.as_ref(),
+            self.ground_station.as_ref(),
+            self.time.time(),
+        );
+    }
+
+    /// Formats the timestamp using the configured time format.
+    pub fn format_time<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        time.format(&self.time_format).to_string()
+    }
+
+    /// Formats the time of day of the timestamp using the time part of the
+    /// configured time format.
+    pub fn format_time_of_day<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> String
+    where
+        Tz::Offset: Display,
+    {
+        time.format(&self.time_of_day_format).to_string()
+    }
+
+    /// Returns the modifier used to emphasize highlighted elements.
+    ///
+    /// This is blinking text unless blinking is disabled.
+    pub fn emphasis(&self) -> Modifier {
+        if self.disable_blink {
+            Modifier::BOLD
+        } else {
+            Modifier::SLOW_BLINK
+        }
+    }
 }
 
-/// Shared time state.
+/// Cache of predicted passes of an object over a ground station.
+///
+/// Passes are computed over a window around the time the cache was last
+/// computed, and recomputed when the object, the station or the time moves
+/// too far from that time.
 #[derive(Default)]
+pub struct PassCache {
+    /// How far ahead of the current time passes are predicted.
+    look_ahead: Duration,
+    /// The maximum elevation in degrees below which passes are discarded.
+    min_elevation: f64,
+    /// The NORAD ID and epoch of the object, and the station position.
+    key: Option<(u64, DateTime<Utc>, Lla)>,
+    /// The time the passes were computed at.
+    base_time: DateTime<Utc>,
+    passes: Vec<Pass>,
+}
+
+impl PassCache {
+    /// How far behind the current time passes are predicted.
+    const LOOK_BEHIND: Duration = Duration::hours(12);
+    /// The maximum time drift before passes are recomputed.
+    const MAX_DRIFT: Duration = Duration::hours(1);
+
+    /// Creates an empty `PassCache` with the given look-ahead duration and
+    /// minimum pass elevation in degrees.
+    pub fn new(look_ahead: Duration, min_elevation: f64) -> Self {
+        Self {
+            // Keep enough look-ahead to cover the timeline window
+            look_ahead: look_ahead.max(Self::MAX_DRIFT * 6),
+            min_elevation,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the cached passes, ordered by AOS.
+    pub fn passes(&self) -> &[Pass] {
+        &self.passes
+    }
+
+    /// Returns the maximum elevation in degrees below which passes are
+    /// discarded.
+    pub fn min_elevation(&self) -> f64 {
+        self.min_elevation
+    }
+
+    /// Sets the minimum pass elevation in degrees, recomputing the passes on
+    /// the next update.
+    pub fn set_min_elevation(&mut self, min_elevation: f64) {
+        self.min_elevation = min_elevation;
+        self.key = None;
+    }
+
+    /// Recomputes the passes if the object, station or time base changed.
+    fn update(&mut self, object: Option<&Object>, station: Option<&Station>, time: DateTime<Utc>) {
+        let (Some(object), Some(station)) = (object, station) else {
+            self.key = None;
+            self.passes.clear();
+            return;
+        };
+
+        let key = (
+            object.elements().norad_id,
+            object.epoch(),
+            station.position.clone(),
+        );
+        if self.key.as_ref() == Some(&key) && (time - self.base_time).abs() <= Self::MAX_DRIFT {
+            return;
+        }
+
+        self.passes = calculate_pass_times(
+            object,
+            &station.position,
+            &(time - Self::LOOK_BEHIND),
+            &(time + self.look_ahead),
+        );
+        self.passes
+            .retain(|pass| pass.max_elevation >= self.min_elevation);
+        self.key = Some(key);
+        self.base_time = time;
+    }
+}
+
+/// A source of the current time.
+pub trait Clock: Send + Sync {
+    /// Returns the current UTC time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// A clock that reads the system time.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that returns the time set by the test, for reproducible tests.
+#[cfg(test)]
+pub struct ManualClock(pub std::sync::Arc<std::sync::Mutex<DateTime<Utc>>>);
+
+#[cfg(test)]
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.0.lock().unwrap()
+    }
+}
+
+/// Shared time state.
 pub struct TimeState {
+    /// The clock providing the current time.
+    clock: Box<dyn Clock>,
     /// Time offset from the current UTC time for time simulation.
     time_offset: Duration,
+    /// The simulation time latched while frozen.
+    frozen_time: Option<DateTime<Utc>>,
+    /// Number of times the simulation time was set, advanced or rewound.
+    revision: u64,
+}
+
+impl Default for TimeState {
+    fn default() -> Self {
+        Self {
+            clock: Box::new(SystemClock),
+            time_offset: Duration::zero(),
+            frozen_time: None,
+            revision: 0,
+        }
+    }
 }
 
 impl TimeState {
+    /// Creates a new `TimeState` reading the current time from `clock`.
+    #[cfg(test)]
+    pub fn with_clock(clock: impl Clock + 'static) -> Self {
+        Self {
+            clock: Box::new(clock),
+            ..Self::default()
+        }
+    }
+
     /// Returns the current simulation time.
+    ///
+    /// While frozen, this is the latched time.
     pub fn time(&self) -> DateTime<Utc> {
-        Utc::now() + self.time_offset
+        self.frozen_time
+            .unwrap_or_else(|| self.clock.now() + self.time_offset)
     }
 
     /// Sets the current simulation time.
     pub fn set_time(&mut self, time: DateTime<Utc>) {
-        self.time_offset = time - Utc::now();
+        self.revision += 1;
+        if self.frozen_time.is_some() {
+            self.frozen_time = Some(time);
+        }
+        self.time_offset = time - self.clock.now();
     }
 
     /// Returns the time offset.
     pub fn time_offset(&self) -> Duration {
-        self.time_offset
+        match self.frozen_time {
+            Some(time) => time - self.clock.now(),
+            None => self.time_offset,
+        }
     }
 
     /// Sets the time offset directly.
     pub fn set_time_offset(&mut self, offset: Duration) {
+        self.revision += 1;
         self.time_offset = offset;
+        if self.frozen_time.is_some() {
+            self.frozen_time = Some(self.clock.now() + offset);
+        }
     }
 
     /// Advances the simulation time.
     pub fn advance_time(&mut self, delta: Duration) {
+        self.revision += 1;
         self.time_offset += delta;
+        if let Some(time) = &mut self.frozen_time {
+            *time += delta;
+        }
     }
 
     /// Rewinds the simulation time.
     pub fn rewind_time(&mut self, delta: Duration) {
-        self.time_offset -= delta;
+        self.advance_time(-delta);
+    }
+
+    /// Returns whether the simulation time is the current time, i.e. the time
+    /// offset is exactly zero.
+    ///
+    /// A frozen time stops being live as soon as the clock moves on, since it
+    /// falls behind the current time.
+    pub fn is_live(&self) -> bool {
+        self.time_offset().is_zero()
+    }
+
+    /// Returns the number of times the simulation time was set, advanced or
+    /// rewound, to tell whether it changed other than by the clock.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Returns whether the simulation time is frozen.
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_time.is_some()
+    }
+
+    /// Freezes the simulation time at the current time, or resumes it from
+    /// the frozen time.
+    pub fn toggle_frozen(&mut self) {
+        match self.frozen_time.take() {
+            Some(time) => self.time_offset = time - self.clock.now(),
+            None => self.frozen_time = Some(self.time()),
+        }
+    }
+
+    /// Resumes the simulation time from the frozen time.
+    pub fn unfreeze(&mut self) {
+        if self.is_frozen() {
+            self.toggle_frozen();
+        }
     }
 }
 
@@ -77,3 +346,129 @@ pub struct Station {
     pub name: String,
     pub position: Lla,
 }
+
+impl Station {
+    /// Creates a new `Station`.
+    ///
+    /// If `name` is not provided, the city name of the position is used.
+    pub fn new(name: Option<String>, position: Lla) -> Self {
+        Self {
+            name: name.unwrap_or_else(|| position.country_city().1),
+            position,
+        }
+    }
+}
+
+/// Reads the ground station position from the environment variables.
+///
+/// The altitude is optional and defaults to 0 km.
+fn position_from_env() -> Option<Lla> {
+    let var = |key: &str| std::env::var(key).ok()?.trim().parse::<f64>().ok();
+
+    let lat = var("TRACKER_STATION_LAT")?;
+    let lon = var("TRACKER_STATION_LON")?;
+    let alt = var("TRACKER_STATION_ALT").unwrap_or(0.0);
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) || alt < 0.0 {
+        return None;
+    }
+    Some(Lla::new(lat, lon, alt))
+}
+
+/// Returns the part of the time format from its first to its last time of day
+/// specifier, without the time zone, or `%H:%M:%S` if it has none.
+///
+/// For example, `%Y-%m-%d %I:%M:%S %p` gives `%I:%M:%S %p`.
+fn time_of_day_format(format: &str) -> String {
+    const TIME_SPECIFIERS: &str = "HIklMSfTRXrpP";
+
+    let mut span: Option<(usize, usize)> = None;
+    let mut chars = format.char_indices();
+    while let Some((start, c)) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+        // Skip the padding and width modifiers, e.g. `%-I` or `%.3f`.
+        let Some((index, specifier)) = chars
+            .by_ref()
+            .find(|(_, c)| !matches!(c, '-' | '_' | '.' | ':' | '#' | '0'..='9'))
+        else {
+            break;
+        };
+        if TIME_SPECIFIERS.contains(specifier) {
+            let end = index + specifier.len_utf8();
+            span = Some((span.map_or(start, |(start, _)| start), end));
+        }
+    }
+    match span {
+        Some((start, end)) => format[start..end].to_owned(),
+        None => "%H:%M:%S".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+    use crate::object::test_object;
+
+    #[test]
+    fn time_state_follows_clock() {
+        let now = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+        let clock = Arc::new(Mutex::new(now));
+        let mut time = TimeState::with_clock(ManualClock(Arc::clone(&clock)));
+        assert_eq!(time.time(), now);
+
+        time.advance_time(Duration::minutes(5));
+        assert_eq!(time.time(), now + Duration::minutes(5));
+        assert_eq!(time.time_offset(), Duration::minutes(5));
+
+        time.set_time(now - Duration::hours(1));
+        assert_eq!(time.time_offset(), -Duration::hours(1));
+        assert!(!time.is_live());
+
+        time.set_time_offset(Duration::zero());
+        assert!(time.is_live());
+
+        // The frozen time falls behind the clock
+        time.toggle_frozen();
+        assert!(time.is_live());
+        *clock.lock().unwrap() += Duration::seconds(1);
+        assert_eq!(time.time(), now);
+        assert_eq!(time.time_offset(), -Duration::seconds(1));
+        assert!(!time.is_live());
+
+        // and resumes from where it was frozen
+        time.toggle_frozen();
+        assert_eq!(time.time(), now);
+        assert!(!time.is_live());
+    }
+
+    #[test]
+    fn time_of_day_is_derived_from_time_format() {
+        assert_eq!(time_of_day_format("%Y-%m-%d %H:%M:%S"), "%H:%M:%S");
+        assert_eq!(time_of_day_format("%Y-%m-%dT%H:%M:%S%:z"), "%H:%M:%S");
+        assert_eq!(time_of_day_format("%Y-%m-%d %I:%M:%S %p"), "%I:%M:%S %p");
+        assert_eq!(time_of_day_format("%-I:%M%P, %d %b"), "%-I:%M%P");
+        assert_eq!(time_of_day_format("%d/%m/%Y"), "%H:%M:%S");
+    }
+
+    #[test]
+    fn low_passes_are_discarded() {
+        let object = test_object(25544);
+        let station = Station::new(Some("Test".into()), Lla::new(45.0, 0.0, 0.0));
+        let time = object.epoch();
+
+        let mut cache = PassCache::new(Duration::hours(24), 0.0);
+        cache.update(Some(&object), Some(&station), time);
+        let all = cache.passes().to_vec();
+        assert!(!all.is_empty());
+
+        cache.set_min_elevation(30.0);
+        cache.update(Some(&object), Some(&station), time);
+        assert!(cache.passes().len() < all.len());
+        assert!(cache.passes().iter().all(|pass| pass.max_elevation >= 30.0));
+    }
+}
@@ -0,0 +1,183 @@
+use std::fmt::Write as _;
+
+use chrono::{Datelike, Timelike};
+use sgp4::Classification;
+
+/// Formats SGP4 elements as a TLE (Two-Line Element set).
+///
+/// The name line is included if the object has a name. Returns `None` if a
+/// field does not fit into the fixed-width TLE format.
+///
+/// See <https://celestrak.org/NORAD/documentation/tle-fmt.php>.
+pub fn format_tle(elements: &sgp4::Elements) -> Option<String> {
+    let norad_id = format_norad_id(elements.norad_id)?;
+    let classification = match elements.classification {
+        Classification::Unclassified => 'U',
+        Classification::Classified => 'C',
+        Classification::Secret => 'S',
+    };
+    let international_designator = elements
+        .international_designator
+        .as_deref()
+        .map(format_international_designator)
+        .unwrap_or_else(|| Some(String::new()))?;
+
+    let datetime = elements.datetime;
+    let seconds = datetime.num_seconds_from_midnight() as f64
+        + datetime.nanosecond() as f64 / 1_000_000_000.0;
+    let day = datetime.ordinal() as f64 + seconds / (24.0 * 60.0 * 60.0);
+    let epoch = format!("{:02}{day:012.8}", datetime.year() % 100);
+
+    if elements.mean_motion_dot.abs() >= 1.0 {
+        return None;
+    }
+    let mean_motion_dot = format!(
+        "{}{}",
+        if elements.mean_motion_dot < 0.0 {
+            '-'
+        } else {
+            ' '
+        },
+        format!("{:.8}", elements.mean_motion_dot.abs()).trim_start_matches('0')
+    );
+
+    let line1 = with_checksum(format!(
+        "1 {norad_id}{classification} {international_designator:<8} {epoch} {mean_motion_dot} {} {} {} {:>4}",
+        format_exponential(elements.mean_motion_ddot)?,
+        format_exponential(elements.drag_term)?,
+        elements.ephemeris_type,
+        elements.element_set_number % 10_000,
+    ));
+    let line2 = with_checksum(format!(
+        "2 {norad_id} {:8.4} {:8.4} {:07} {:8.4} {:8.4} {:11.8}{:>5}",
+        elements.inclination,
+        elements.right_ascension,
+        (elements.eccentricity * 1e7).round() as u32,
+        elements.argument_of_perigee,
+        elements.mean_anomaly,
+        elements.mean_motion,
+        elements.revolution_number % 100_000,
+    ));
+    if line1.len() != 69 || line2.len() != 69 {
+        return None;
+    }
+
+    let mut tle = String::new();
+    if let Some(name) = &elements.object_name {
+        writeln!(tle, "{name}").unwrap();
+    }
+    writeln!(tle, "{line1}").unwrap();
+    write!(tle, "{line2}").unwrap();
+    Some(tle)
+}
+
+/// Formats a NORAD catalog ID, using the Alpha-5 scheme for IDs above 99999.
+fn format_norad_id(norad_id: u64) -> Option<String> {
+    // Alpha-5 letters, skipping `I` and `O` to avoid confusion with digits
+    const ALPHA5_LETTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ";
+
+    if norad_id < 100_000 {
+        return Some(format!("{norad_id:05}"));
+    }
+    let letter = ALPHA5_LETTERS.get((norad_id / 10_000 - 10) as usize)?;
+    Some(format!("{}{:04}", *letter as char, norad_id % 10_000))
+}
+
+/// Formats an international designator such as `1998-067A` as `98067A`.
+fn format_international_designator(designator: &str) -> Option<String> {
+    let (year, rest) = designator.split_once('-')?;
+    let year = year.get(2..4)?;
+    (rest.len() <= 6).then(|| format!("{year}{rest}"))
+}
+
+/// Formats a value in the TLE exponential notation with an assumed leading
+/// decimal point, e.g. `-0.11606e-4` as `-11606-4`.
+fn format_exponential(value: f64) -> Option<String> {
+    let sign = if value < 0.0 { '-' } else { ' ' };
+    if value == 0.0 {
+        return Some(format!("{sign}00000-0"));
+    }
+
+    let mut exponent = value.abs().log10().floor() as i32 + 1;
+    let mut mantissa = (value.abs() / 10_f64.powi(exponent) * 1e5).round() as u32;
+    if mantissa >= 100_000 {
+        mantissa /= 10;
+        exponent += 1;
+    }
+    if !(-9..=9).contains(&exponent) {
+        return None;
+    }
+    let exponent_sign = if exponent < 0 { '-' } else { '+' };
+    Some(format!(
+        "{sign}{mantissa:05}{exponent_sign}{}",
+        exponent.abs()
+    ))
+}
+
+/// Appends the modulo 10 checksum to a TLE line.
+fn with_checksum(line: String) -> String {
+    let checksum: u32 = line
+        .chars()
+        .map(|c| match c {
+            '-' => 1,
+            c => c.to_digit(10).unwrap_or(0),
+        })
+        .sum();
+    format!("{line}{}", checksum % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses the TLE, formats it again and checks that the lines match.
+    fn assert_round_trip(name: &str, line1: &str, line2: &str) -> sgp4::Elements {
+        let elements =
+            sgp4::Elements::from_tle(Some(name.into()), line1.as_bytes(), line2.as_bytes())
+                .unwrap();
+        let tle = format_tle(&elements).unwrap();
+        assert_eq!(tle, format!("{name}\n{line1}\n{line2}"));
+
+        // The formatted lines, including their checksums, parse again
+        let lines: Vec<_> = tle.lines().collect();
+        let parsed =
+            sgp4::Elements::from_tle(None, lines[1].as_bytes(), lines[2].as_bytes()).unwrap();
+        assert_eq!(parsed.norad_id, elements.norad_id);
+        assert_eq!(parsed.datetime, elements.datetime);
+        assert_eq!(parsed.mean_motion, elements.mean_motion);
+        elements
+    }
+
+    #[test]
+    fn iss_tle_is_formatted() {
+        assert_round_trip(
+            "ISS (ZARYA)",
+            "1 25544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2927",
+            "2 25544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563537",
+        );
+    }
+
+    #[test]
+    fn alpha5_tle_is_formatted() {
+        let elements = assert_round_trip(
+            "TEST",
+            "1 A5544U 98067A   08264.51782528 -.00002182  00000-0 -11606-4 0  2925",
+            "2 A5544  51.6416 247.4627 0006703 130.5360 325.0288 15.72125391563535",
+        );
+        assert_eq!(elements.norad_id, 105544);
+    }
+
+    #[test]
+    fn norad_id_uses_alpha5_above_99999() {
+        assert_eq!(format_norad_id(25544).as_deref(), Some("25544"));
+        assert_eq!(format_norad_id(100_000).as_deref(), Some("A0000"));
+        assert_eq!(format_norad_id(182_345).as_deref(), Some("J2345"));
+        assert_eq!(format_norad_id(339_999).as_deref(), Some("Z9999"));
+        assert_eq!(format_norad_id(340_000), None);
+    }
+
+    #[test]
+    fn checksum_counts_digits_and_minus_signs() {
+        assert_eq!(with_checksum("1-2 A3".into()), "1-2 A37");
+    }
+}
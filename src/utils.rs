@@ -4,7 +4,10 @@ use rayon::prelude::*;
 
 use std::f64::consts::{PI, TAU};
 
-use crate::{coordinates::Lla, object::Object};
+use crate::{
+    coordinates::{Lla, wgs84},
+    object::Object,
+};
 
 /// Returns the Epoch for the given UTC timestamp.
 pub fn epoch_from_utc(time: &DateTime<Utc>) -> Epoch {
@@ -75,6 +78,34 @@ pub fn subsolar_point(time: &DateTime<Utc>) -> (f64, f64) {
     (lon, decl)
 }
 
+/// Returns whether the position is illuminated by the Sun at the given UTC
+/// timestamp.
+///
+/// Uses a cylindrical model of the Earth's shadow.
+pub fn is_sunlit(position: &Lla, time: &DateTime<Utc>) -> bool {
+    let (sub_lon, sub_lat) = subsolar_point(time);
+    let sun = [
+        sub_lat.cos() * sub_lon.cos(),
+        sub_lat.cos() * sub_lon.sin(),
+        sub_lat.sin(),
+    ];
+    let ecef = position.to_ecef();
+    let r = [ecef.x, ecef.y, ecef.z];
+
+    // Projection of the position onto the Sun direction
+    let projection: f64 = r.iter().zip(sun).map(|(r, s)| r * s).sum();
+    if projection >= 0.0 {
+        return true;
+    }
+    // Distance from the shadow axis
+    let distance_sq: f64 = r
+        .iter()
+        .zip(sun)
+        .map(|(r, s)| (r - projection * s).powi(2))
+        .sum();
+    distance_sq > wgs84::MEAN_RADIUS.powi(2)
+}
+
 /// Calculates a set of points representing the day-night terminator.
 ///
 /// # Arguments
@@ -105,27 +136,104 @@ pub fn calculate_terminator(time: &DateTime<Utc>) -> Vec<(f64, f64)> {
 }
 
 /// Calculates ground track points of the object.
-pub fn calculate_ground_track(object: &Object, time: &DateTime<Utc>) -> Vec<(f64, f64)> {
+///
+/// # Returns
+///
+/// A vector of `(longitude, latitude, is_sunlit)` tuples, where longitude and
+/// latitude are in degrees and `is_sunlit` indicates whether the object is
+/// illuminated by the Sun at that point.
+pub fn calculate_ground_track(object: &Object, time: &DateTime<Utc>) -> Vec<(f64, f64, bool)> {
     (1..object.orbital_period().num_minutes())
         .into_par_iter()
         .map(|mins| {
-            let state = object.predict(&(*time + Duration::minutes(mins))).unwrap();
-            (state.longitude(), state.latitude())
+            let time = *time + Duration::minutes(mins);
+            let state = object.predict(&time).unwrap();
+            (
+                state.longitude(),
+                state.latitude(),
+                is_sunlit(&state.position, &time),
+            )
+        })
+        .collect()
+}
+
+/// Calculates the orbit of an object over one orbital period in an inertial
+/// frame, drawn over the Earth as oriented at the given time.
+///
+/// Unlike the ground track, the Earth rotation during the period is ignored,
+/// so the track follows the fixed orbital plane and passes through the
+/// current position of the object.
+///
+/// # Returns
+///
+/// A vector of `(longitude, latitude, is_sunlit)` tuples, as returned by
+/// [`calculate_ground_track`].
+pub fn calculate_inertial_track(object: &Object, time: &DateTime<Utc>) -> Vec<(f64, f64, bool)> {
+    let gmst = gmst_from_jd_tt(epoch_from_utc(time).to_jde_tt_days());
+    (0..=object.orbital_period().num_minutes())
+        .into_par_iter()
+        .map(|mins| {
+            let time = *time + Duration::minutes(mins);
+            let position = object.predict_teme(&time).unwrap().to_ecef(gmst).to_lla();
+            (position.lon, position.lat, is_sunlit(&position, &time))
         })
         .collect()
 }
 
 /// Calculates the visibility circle for a point on the Earth's surface.
 ///
+/// The circle contains the ground positions from which the point is seen at an
+/// elevation of at least `min_elevation_deg`, on a spherical Earth of radius
+/// `earth_radius` in km.
+pub fn calculate_visibility_area(
+    position: &Lla,
+    min_elevation_deg: f64,
+    earth_radius: f64,
+) -> Vec<(f64, f64)> {
+    let min_elevation_rad = min_elevation_deg.to_radians();
+    let cos_c = earth_radius * min_elevation_rad.cos() / (earth_radius + position.alt.max(0.1));
+    let central_angle_rad = (cos_c.acos() - min_elevation_rad).max(0.0);
+    calculate_circle(
+        position.lat.to_radians(),
+        position.lon.to_radians(),
+        central_angle_rad,
+    )
+}
+
+/// Calculates the line on the night side where the Sun is `depression_deg`
+/// degrees below the horizon, e.g. 6° for the edge of civil twilight.
+///
+/// # Returns
+///
+/// A vector of `(longitude, latitude)` pairs in degrees, representing a circle
+/// around the anti-solar point.
+pub fn calculate_twilight(time: &DateTime<Utc>, depression_deg: f64) -> Vec<(f64, f64)> {
+    let (lon, lat) = antisolar_point(time);
+    calculate_circle(lat, lon, (90.0 - depression_deg).to_radians())
+}
+
+/// Calculates the antisolar point, the point on the Earth's surface
+/// opposite to the subsolar point, at the given UTC timestamp.
+///
+/// # Returns
+///
+/// A tuple `(longitude, latitude)` in radians.
+pub fn antisolar_point(time: &DateTime<Utc>) -> (f64, f64) {
+    let (sub_lon, sub_lat) = subsolar_point(time);
+    (wrap_longitude_rad(sub_lon + PI), -sub_lat)
+}
+
+/// Calculates the points at the given central angle around a center point,
+/// with latitude and longitude in radians.
+///
 /// See <https://en.wikipedia.org/wiki/Great-circle_distance>.
-pub fn calculate_visibility_area(position: &Lla) -> Vec<(f64, f64)> {
+///
+/// # Returns
+///
+/// A vector of `(longitude, latitude)` pairs in degrees.
+fn calculate_circle(lat0_rad: f64, lon0_rad: f64, central_angle_rad: f64) -> Vec<(f64, f64)> {
     const AZIMUTH_STEP: usize = 10;
 
-    let lat0_rad = position.lat.to_radians();
-    let lon0_rad = position.lon.to_radians();
-    let earth_radius = 6371.0088_f64; // mean Earth radius in km
-    let cos_c = earth_radius / (earth_radius + position.alt.max(0.1));
-    let central_angle_rad = cos_c.acos();
     let mut points = Vec::with_capacity(360 / AZIMUTH_STEP);
     for az in (-180..=180)
         .step_by(AZIMUTH_STEP)
@@ -144,16 +252,21 @@ pub fn calculate_visibility_area(position: &Lla) -> Vec<(f64, f64)> {
     points
 }
 
-/// Calculates sky track points for the object as seen from a ground station.
+/// Number of minutes before and after the current time covered by the sky
+/// track.
+pub const SKY_TRACK_WINDOW_MINUTES: i64 = 30;
+
+/// Calculates sky track points for the object as seen from a ground station,
+/// on a polar plot rotated so that the azimuth `rotation_deg` is up.
 pub fn calculate_sky_track(
     object: &Object,
     ground_station: &Lla,
     time: &DateTime<Utc>,
+    rotation_deg: f64,
 ) -> Vec<(f64, f64)> {
-    const WINDOW_MINUTES: i64 = 30;
     const STEP_MIN: usize = 1;
 
-    (-WINDOW_MINUTES..=WINDOW_MINUTES)
+    (-SKY_TRACK_WINDOW_MINUTES..=SKY_TRACK_WINDOW_MINUTES)
         .step_by(STEP_MIN)
         .collect::<Vec<_>>()
         .into_par_iter()
@@ -163,55 +276,214 @@ pub fn calculate_sky_track(
             if el < 0.0 {
                 None
             } else {
-                Some(az_el_to_canvas(az, el))
+                Some(az_el_to_canvas(az - rotation_deg, el))
             }
         })
         .collect()
 }
 
+/// Calculates the elevation of the object above the ground station over the
+/// window of the sky track, as (minutes from `time`, elevation in degrees)
+/// pairs.
+pub fn calculate_elevation_profile(
+    object: &Object,
+    ground_station: &Lla,
+    time: &DateTime<Utc>,
+) -> Vec<(f64, f64)> {
+    (-SKY_TRACK_WINDOW_MINUTES..=SKY_TRACK_WINDOW_MINUTES)
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|mins| {
+            let state = object.predict(&(*time + Duration::minutes(mins))).unwrap();
+            let (_, el) = state.position.az_el(ground_station);
+            (mins as f64, el)
+        })
+        .collect()
+}
+
+/// A pass of an object over an observer.
+#[derive(Clone, Debug)]
+pub struct Pass {
+    /// Acquisition of signal, when the object rises above the horizon.
+    pub aos: DateTime<Utc>,
+    /// Loss of signal, when the object sets below the horizon.
+    pub los: DateTime<Utc>,
+    /// Maximum elevation in degrees during the pass.
+    pub max_elevation: f64,
+    /// Minimum and maximum range rate in km/s during the pass, negative while
+    /// the object approaches.
+    pub range_rate: (f64, f64),
+    /// Slant range in km at AOS.
+    pub aos_range: f64,
+    /// Slant range in km at the maximum elevation.
+    pub max_elevation_range: f64,
+    /// Slant range in km at LOS.
+    pub los_range: f64,
+    /// Whether the pass is clipped by the window edges, in which case the
+    /// AOS, LOS and their ranges are those at the edges.
+    pub clipped: bool,
+}
+
+impl Pass {
+    /// Returns the maximum and minimum doppler shift in Hz during the pass for
+    /// a signal at the given frequency in Hz.
+    pub fn doppler_shift(&self, frequency: f64) -> (f64, f64) {
+        const SPEED_OF_LIGHT: f64 = 299_792.458; // km/s
+
+        let (min_rate, max_rate) = self.range_rate;
+        (
+            -min_rate / SPEED_OF_LIGHT * frequency,
+            -max_rate / SPEED_OF_LIGHT * frequency,
+        )
+    }
+}
+
 /// Calculates satellite pass time segments within a given time window.
+///
+/// Passes in progress at the window edges are clipped to the window.
 pub fn calculate_pass_times(
     object: &Object,
     observer: &Lla,
     start_time: &DateTime<Utc>,
     end_time: &DateTime<Utc>,
-) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+) -> Vec<Pass> {
     debug_assert!(start_time <= end_time);
 
     const TIME_STEP: Duration = Duration::minutes(1);
 
     let mut pass_segments = Vec::new();
-    let mut current_pass_start: Option<DateTime<Utc>> = None;
+    let mut current_pass: Option<Pass> = None;
+    let mut previous_range = None;
 
     let mut time = *start_time;
     while time <= *end_time {
         let state = object.predict(&time).unwrap();
         let (_, el) = state.position.az_el(observer);
+        let range = state.position.range(observer);
+        let range_rate = previous_range
+            .map(|previous| (range - previous) / TIME_STEP.as_seconds_f64())
+            .unwrap_or(0.0);
+        previous_range = Some(range);
         let is_visible = el >= 0.0;
 
-        match (current_pass_start, is_visible) {
+        match (&mut current_pass, is_visible) {
             (None, true) => {
                 // Start of a new pass
-                current_pass_start = Some(time);
+                current_pass = Some(Pass {
+                    aos: time,
+                    los: time,
+                    max_elevation: el,
+                    range_rate: (range_rate, range_rate),
+                    aos_range: range,
+                    max_elevation_range: range,
+                    los_range: range,
+                    clipped: time == *start_time,
+                });
             }
-            (Some(start), false) => {
+            (Some(pass), true) => {
+                pass.los = time;
+                pass.los_range = range;
+                if el > pass.max_elevation {
+                    pass.max_elevation = el;
+                    pass.max_elevation_range = range;
+                }
+                pass.range_rate = (
+                    pass.range_rate.0.min(range_rate),
+                    pass.range_rate.1.max(range_rate),
+                );
+            }
+            (Some(_), false) => {
                 // End of current pass
-                pass_segments.push((start, time - TIME_STEP));
-                current_pass_start = None;
+                pass_segments.extend(current_pass.take());
             }
-            _ => {}
+            (None, false) => {}
         }
 
         time += TIME_STEP;
     }
 
-    if let Some(start) = current_pass_start {
-        pass_segments.push((start, *end_time));
+    if let Some(mut pass) = current_pass {
+        pass.los = *end_time;
+        pass.clipped = true;
+        pass_segments.push(pass);
     }
 
     pass_segments
 }
 
+/// Calculates the closest approach between two objects within a given time
+/// window.
+///
+/// Returns the time of the closest approach and the distance between the
+/// objects in km, or `None` if the window is empty.
+pub fn calculate_closest_approach(
+    object: &Object,
+    other: &Object,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+) -> Option<(DateTime<Utc>, f64)> {
+    const TIME_STEP: Duration = Duration::minutes(1);
+
+    find_minimum(
+        |time| {
+            let position = object.predict(time).unwrap().position;
+            position.range(&other.predict(time).unwrap().position)
+        },
+        start_time,
+        end_time,
+        TIME_STEP,
+    )
+}
+
+/// Finds the minimum of a function of time within a given time window.
+///
+/// The function is sampled at the given step, then the minimum is refined
+/// around the smallest sample with a golden-section search.
+fn find_minimum(
+    f: impl Fn(&DateTime<Utc>) -> f64,
+    start_time: &DateTime<Utc>,
+    end_time: &DateTime<Utc>,
+    step: Duration,
+) -> Option<(DateTime<Utc>, f64)> {
+    const TOLERANCE: Duration = Duration::milliseconds(100);
+    // The inverse of the golden ratio
+    const INV_PHI: f64 = 0.618_033_988_749_895;
+
+    let mut best: Option<(DateTime<Utc>, f64)> = None;
+    let mut time = *start_time;
+    while time <= *end_time {
+        let value = f(&time);
+        if best.is_none_or(|(_, min)| value < min) {
+            best = Some((time, value));
+        }
+        time += step;
+    }
+    let (best_time, best_value) = best?;
+
+    let at = |offset: f64| best_time + Duration::milliseconds((offset * 1000.0) as i64);
+    let (mut low, mut high) = (
+        ((*start_time).max(best_time - step) - best_time).as_seconds_f64(),
+        ((*end_time).min(best_time + step) - best_time).as_seconds_f64(),
+    );
+    while high - low > TOLERANCE.as_seconds_f64() {
+        let left = high - (high - low) * INV_PHI;
+        let right = low + (high - low) * INV_PHI;
+        if f(&at(left)) < f(&at(right)) {
+            high = right;
+        } else {
+            low = left;
+        }
+    }
+
+    let refined_time = at((low + high) / 2.0);
+    let refined_value = f(&refined_time);
+    Some(if refined_value < best_value {
+        (refined_time, refined_value)
+    } else {
+        (best_time, best_value)
+    })
+}
+
 /// Converts azimuth and elevation to canvas coordinates.
 ///
 /// Canvas is a unit circle using a Cartesian coordinate system.
@@ -242,12 +514,227 @@ fn cartesian_to_polar(x: f64, y: f64) -> (f64, f64) {
     (r, theta)
 }
 
-/// Wraps a value to the range [-180, 180].
+/// Interpolates between two points along the great circle connecting them.
+///
+/// The points are `(longitude, latitude)` tuples in degrees, and `t` is the
+/// fraction of the way from `from` to `to`.
+///
+/// See <https://en.wikipedia.org/wiki/Slerp>.
+pub fn interpolate_great_circle(from: (f64, f64), to: (f64, f64), t: f64) -> (f64, f64) {
+    let to_vector = |(lon, lat): (f64, f64)| {
+        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+        [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+    };
+    let v1 = to_vector(from);
+    let v2 = to_vector(to);
+
+    let dot = v1.iter().zip(&v2).map(|(a, b)| a * b).sum::<f64>();
+    let angle = dot.clamp(-1.0, 1.0).acos();
+    // Fall back to linear interpolation for (nearly) coincident or antipodal
+    // points, where the great circle is undefined
+    if angle < 1e-9 || (PI - angle) < 1e-9 {
+        return (from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t);
+    }
+
+    let a = ((1.0 - t) * angle).sin() / angle.sin();
+    let b = (t * angle).sin() / angle.sin();
+    let [x, y, z] = [0, 1, 2].map(|i| a * v1[i] + b * v2[i]);
+    (
+        y.atan2(x).to_degrees(),
+        z.atan2((x * x + y * y).sqrt()).to_degrees(),
+    )
+}
+
+/// Returns the central angle in radians between two points.
+///
+/// The points are `(longitude, latitude)` tuples in degrees.
+pub fn central_angle(from: (f64, f64), to: (f64, f64)) -> f64 {
+    let (lon1, lat1) = (from.0.to_radians(), from.1.to_radians());
+    let (lon2, lat2) = (to.0.to_radians(), to.1.to_radians());
+    (lat1.sin() * lat2.sin() + lat1.cos() * lat2.cos() * (lon2 - lon1).cos())
+        .clamp(-1.0, 1.0)
+        .acos()
+}
+
+/// Calculates the initial bearing of the great circle from one point to
+/// another, in degrees clockwise from north in the range [0, 360).
+///
+/// The points are `(longitude, latitude)` tuples in degrees.
+pub fn initial_bearing(from: (f64, f64), to: (f64, f64)) -> f64 {
+    // Wrap the difference so that crossing the antimeridian takes the short way
+    let delta_lon = wrap_longitude_deg(to.0 - from.0).to_radians();
+    let (lat1, lat2) = (from.1.to_radians(), to.1.to_radians());
+    let y = delta_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
+    y.atan2(x).to_degrees().rem_euclid(360.0)
+}
+
+/// The abbreviations of the 8-point compass directions, clockwise from north.
+pub const COMPASS_POINTS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// Returns the index in [`COMPASS_POINTS`] of the compass direction nearest to
+/// a bearing in degrees.
+pub fn compass_point(bearing: f64) -> usize {
+    ((bearing.rem_euclid(360.0) / 45.0).round() as usize) % 8
+}
+
+/// Wraps a value to the range [-180, 180).
 pub fn wrap_longitude_deg(lon: f64) -> f64 {
     (lon + 180.0).rem_euclid(360.0) - 180.0
 }
 
-/// Wraps a value to the range [-π, π].
+/// Wraps a value to the range [-π, π).
 pub fn wrap_longitude_rad(lon: f64) -> f64 {
     (lon + PI).rem_euclid(TAU) - PI
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn az_el_canvas_round_trip() {
+        for el in (0..90).step_by(5).map(f64::from) {
+            for az in (0..360).step_by(15).map(f64::from) {
+                let (x, y) = az_el_to_canvas(az, el);
+                let (az2, el2) = canvas_to_az_el(x, y);
+                assert!((el - el2).abs() < 1e-9, "el {el} became {el2}");
+                assert!((az - az2).abs() < 1e-9, "az {az} became {az2}");
+            }
+        }
+        // The azimuth is undefined at the zenith
+        let (x, y) = az_el_to_canvas(123.0, 90.0);
+        assert!((canvas_to_az_el(x, y).1 - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn wrap_longitude_is_idempotent() {
+        for lon in (-1080..=1080).step_by(45).map(f64::from) {
+            let wrapped = wrap_longitude_deg(lon);
+            assert!((-180.0..180.0).contains(&wrapped));
+            assert_eq!(wrap_longitude_deg(wrapped), wrapped);
+            assert!(((lon - wrapped) / 360.0).fract().abs() < 1e-9);
+
+            let wrapped = wrap_longitude_rad(lon.to_radians());
+            assert!((-PI..PI).contains(&wrapped));
+            assert!((wrap_longitude_rad(wrapped) - wrapped).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gmst_matches_reference() {
+        // Meeus, Astronomical Algorithms, example 12.a: 1987-04-10 0h UT. The
+        // formula is evaluated at the TT instant, which is ahead of UTC by
+        // 55.184 s (32.184 s plus 23 leap seconds) at that date
+        let time = Utc.with_ymd_and_hms(1987, 4, 10, 0, 0, 0).unwrap();
+        let jd_tt = epoch_from_utc(&time).to_jde_tt_days();
+        assert!((jd_tt - (2446895.5 + 55.184 / 86400.0)).abs() < 1e-9);
+        let gmst = gmst_from_jd_tt(jd_tt).to_degrees();
+        let expected =
+            (13.0 + 10.0 / 60.0 + 46.3668 / 3600.0) * 15.0 + 55.184 / 86400.0 * 360.98564736629;
+        assert!((gmst - expected).abs() < 1e-6, "{gmst} != {expected}");
+    }
+
+    #[test]
+    fn visibility_radius_matches_central_angle() {
+        let position = Lla::new(30.0, 100.0, 400.0);
+        for earth_radius in [wgs84::MEAN_RADIUS, wgs84::A, 6000.0] {
+            let expected = (earth_radius / (earth_radius + position.alt)).acos();
+            for point in calculate_visibility_area(&position, 0.0, earth_radius) {
+                let angle = central_angle((position.lon, position.lat), point);
+                assert!((angle - expected).abs() < 1e-9, "{angle} != {expected}");
+            }
+        }
+    }
+
+    #[test]
+    fn mean_radius_matches_iugg_value() {
+        assert!((wgs84::MEAN_RADIUS - 6371.0088).abs() < 1e-4);
+    }
+
+    #[test]
+    fn visibility_radius_shrinks_with_min_elevation() {
+        let position = Lla::new(0.0, 0.0, 400.0);
+        let radius = |min_elevation: f64| {
+            let points = calculate_visibility_area(&position, min_elevation, wgs84::MEAN_RADIUS);
+            central_angle((position.lon, position.lat), points[0])
+        };
+        let (horizon, elevated) = (radius(0.0), radius(10.0));
+        assert!(elevated < horizon, "{elevated} >= {horizon}");
+
+        let min_elevation = 10f64.to_radians();
+        let expected =
+            (wgs84::MEAN_RADIUS * min_elevation.cos() / (wgs84::MEAN_RADIUS + position.alt)).acos()
+                - min_elevation;
+        assert!(
+            (elevated - expected).abs() < 1e-9,
+            "{elevated} != {expected}"
+        );
+        // About 2200 km and 1340 km on the ground for the ISS
+        assert!((horizon.to_degrees() - 19.8).abs() < 0.1);
+        assert!((elevated.to_degrees() - 12.1).abs() < 0.1);
+    }
+
+    #[test]
+    fn bearing_crosses_antimeridian_eastward() {
+        let bearing = initial_bearing((179.0, 0.0), (-179.0, 0.0));
+        assert!((bearing - 90.0).abs() < 1e-9, "{bearing}");
+        let bearing = initial_bearing((-179.0, 0.0), (179.0, 0.0));
+        assert!((bearing - 270.0).abs() < 1e-9, "{bearing}");
+    }
+
+    #[test]
+    fn compass_point_rounds_to_nearest_direction() {
+        assert_eq!(compass_point(0.0), 0);
+        assert_eq!(compass_point(44.0), 1);
+        assert_eq!(compass_point(200.0), 4);
+        assert_eq!(compass_point(350.0), 0);
+        assert_eq!(compass_point(-45.0), 7);
+    }
+
+    #[test]
+    fn minimum_is_refined_between_samples() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let end = start + Duration::hours(2);
+        let expected = start + Duration::seconds(37 * 60 + 25);
+        // A distance with a local minimum before the global one
+        let f = |time: &DateTime<Utc>| {
+            let seconds = (*time - expected).as_seconds_f64();
+            seconds.abs().min((seconds + 1800.0).abs() + 10.0)
+        };
+
+        let (time, value) = find_minimum(f, &start, &end, Duration::minutes(1)).unwrap();
+        assert!((time - expected).abs() < Duration::seconds(1), "{time}");
+        assert!(value < 1.0, "{value}");
+        assert!(find_minimum(f, &end, &start, Duration::minutes(1)).is_none());
+    }
+
+    #[test]
+    fn inertial_track_closes_after_one_period() {
+        let object = crate::object::test_object(25544);
+        let time = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let state = object.predict(&time).unwrap();
+
+        let track = calculate_inertial_track(&object, &time);
+        let (first, last) = (track.first().unwrap(), track.last().unwrap());
+        assert!(central_angle((first.0, first.1), (state.longitude(), state.latitude())) < 1e-9);
+        // Unlike the ground track, shifted west by the Earth rotation
+        assert!(central_angle((first.0, first.1), (last.0, last.1)).to_degrees() < 5.0);
+    }
+
+    #[test]
+    fn pass_range_is_minimal_at_max_elevation() {
+        let object = crate::object::test_object(25544);
+        let station = Lla::new(45.0, 0.0, 0.0);
+        let start = object.epoch();
+        let passes = calculate_pass_times(&object, &station, &start, &(start + Duration::days(1)));
+
+        let pass = passes.iter().find(|pass| !pass.clipped).unwrap();
+        assert!(pass.max_elevation_range < pass.aos_range);
+        assert!(pass.max_elevation_range < pass.los_range);
+        // The object is near the horizon at AOS and LOS
+        assert!(pass.aos_range > 1000.0, "{}", pass.aos_range);
+    }
+}
@@ -1,13 +1,36 @@
 use ratatui::prelude::*;
 
+pub mod about;
+pub mod context_menu;
 pub mod information;
 pub mod keymap;
+pub mod log_panel;
+pub mod next_pass;
+pub mod objects_table;
+pub mod planes;
 pub mod satellite_groups;
 pub mod sky;
 pub mod tabs;
 pub mod timeline;
+pub mod toast;
 pub mod world_map;
 
+/// Returns the border style of a panel, highlighting the focused panel.
+fn border_style(focused: bool) -> Style {
+    if focused {
+        Style::new().light_cyan()
+    } else {
+        Style::new()
+    }
+}
+
+/// Returns a rectangle of the given size centered in `area`, clamped to it.
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    Rect::new(x, y, width.min(area.width), height.min(area.height))
+}
+
 /// Converts window coordinates to area coordinates.
 #[must_use]
 fn window_to_area(global: Position, area: Rect) -> Option<Position> {
@@ -0,0 +1,184 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph},
+};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    config::Config,
+    format::format_number,
+    group::Group,
+    shared_state::SharedState,
+    widgets::{centered_rect, satellite_groups::SatelliteGroupsState},
+};
+
+/// A popup that displays the version of the application, where its data
+/// comes from and how much of it is loaded.
+pub struct About<'a> {
+    pub state: &'a AboutState,
+    pub shared: &'a SharedState,
+    pub groups: &'a SatelliteGroupsState,
+}
+
+/// State of an [`About`] popup.
+#[derive(Default)]
+pub struct AboutState {
+    /// Whether the popup is displayed.
+    open: bool,
+    /// The total size in bytes of the cache files when the popup was opened,
+    /// `None` if the cache directory cannot be read.
+    cache_size: Option<u64>,
+}
+
+impl AboutState {
+    /// Opens the popup, measuring the cache, or closes it.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        if self.open {
+            self.cache_size = directory_size(&Group::cache_dir());
+        }
+    }
+
+    /// Closes the popup.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Returns whether the popup is displayed.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+}
+
+impl Widget for About<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let entries = self.entries();
+        let key_width = entries
+            .iter()
+            .map(|(key, _)| key.width())
+            .max()
+            .unwrap_or(0);
+        let value_width = entries
+            .iter()
+            .map(|(_, value)| value.width())
+            .max()
+            .unwrap_or(0);
+
+        let lines: Vec<_> = entries
+            .into_iter()
+            .map(|(key, value)| {
+                Line::from(vec![
+                    Span::styled(format!("{key:<key_width$}"), Style::default().cyan()),
+                    Span::raw(" "),
+                    Span::raw(value),
+                ])
+            })
+            .collect();
+
+        const BORDER_WIDTH: u16 = 1;
+        let popup_area = centered_rect(
+            (key_width + 1 + value_width) as u16 + BORDER_WIDTH * 2,
+            lines.len() as u16 + BORDER_WIDTH * 2,
+            area,
+        );
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines)
+            .block(Block::bordered().title(t!("about-title").to_string().blue()))
+            .render(popup_area, buf);
+    }
+}
+
+impl About<'_> {
+    /// Returns the displayed entries, followed by the number of loaded objects
+    /// of each selected group.
+    fn entries(&self) -> Vec<(String, String)> {
+        let config = match Config::path() {
+            Some(path) if path.exists() => path.display().to_string(),
+            Some(path) => format!("{} ({})", path.display(), t!("about-config_missing")),
+            None => t!("about-config_missing").into_owned(),
+        };
+        let cache_size = match self.state.cache_size {
+            Some(size) => format_size(size),
+            None => t!("about-cache_empty").into_owned(),
+        };
+
+        let mut entries = vec![
+            (t!("about-version").into(), env!("CARGO_PKG_VERSION").into()),
+            (t!("about-config").into(), config),
+            (
+                t!("about-provider").into(),
+                format!("{} ({})", Group::PROVIDER, Group::ELEMENTS_URL),
+            ),
+            (
+                t!("about-cache").into(),
+                format!("{} ({cache_size})", Group::cache_dir().display()),
+            ),
+            (t!("about-locale").into(), rust_i18n::locale().to_string()),
+            (
+                t!("about-objects").into(),
+                self.shared.objects.len().to_string(),
+            ),
+        ];
+
+        let mut counts = HashMap::new();
+        for group in self
+            .shared
+            .objects
+            .iter()
+            .filter_map(|object| object.group())
+        {
+            *counts.entry(group).or_insert(0usize) += 1;
+        }
+        entries.extend(self.groups.selected_groups().into_iter().map(|label| {
+            let count = counts.get(label.as_str()).copied().unwrap_or(0);
+            (format!("  {label}"), count.to_string())
+        }));
+        entries
+    }
+}
+
+/// Returns the total size in bytes of the files in a directory, or `None` if
+/// it cannot be read.
+fn directory_size(path: &Path) -> Option<u64> {
+    let entries = fs::read_dir(path).ok()?;
+    Some(
+        entries
+            .filter_map(|entry| entry.ok()?.metadata().ok())
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len())
+            .sum(),
+    )
+}
+
+/// Formats a size in bytes with binary units, e.g. `1.5 MiB`.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{} {}", format_number(size, Some(1)), UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_uses_binary_units() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
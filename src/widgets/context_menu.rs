@@ -0,0 +1,206 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, List, ListItem, ListState, StatefulWidget},
+};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::States,
+    event::Event,
+    object::Object,
+    widgets::{information, tabs::Tab, window_to_area},
+};
+
+/// A popup menu of actions on an object, opened at the cursor.
+pub struct ContextMenu<'a> {
+    pub state: &'a mut ContextMenuState,
+}
+
+/// State of a [`ContextMenu`] widget.
+#[derive(Default)]
+pub struct ContextMenuState {
+    /// The object the menu acts on, or `None` if the menu is closed.
+    object: Option<Object>,
+    /// The position of the cursor when the menu was opened.
+    position: Position,
+    /// The current state of the list widget.
+    list_state: ListState,
+    /// The inner rendering area of the widget.
+    inner_area: Rect,
+}
+
+impl ContextMenuState {
+    /// Opens the menu for the object at the given window position.
+    pub fn open(&mut self, object: Object, position: Position) {
+        self.object = Some(object);
+        self.position = position;
+        self.list_state.select_first();
+    }
+
+    /// Closes the menu.
+    pub fn close(&mut self) {
+        self.object = None;
+    }
+
+    /// Returns whether the menu is open.
+    pub fn is_open(&self) -> bool {
+        self.object.is_some()
+    }
+
+    fn select_previous(&mut self) {
+        let index = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some((index + Action::ALL.len() - 1) % Action::ALL.len()));
+    }
+
+    fn select_next(&mut self) {
+        let index = self.list_state.selected().unwrap_or(0);
+        self.list_state
+            .select(Some((index + 1) % Action::ALL.len()));
+    }
+}
+
+/// An action of the context menu.
+#[derive(Clone, Copy)]
+enum Action {
+    Follow,
+    CopyTle,
+    ShowPasses,
+    OpenDetails,
+}
+
+impl Action {
+    const ALL: [Self; 4] = [
+        Self::Follow,
+        Self::CopyTle,
+        Self::ShowPasses,
+        Self::OpenDetails,
+    ];
+
+    fn label(self) -> String {
+        match self {
+            Self::Follow => t!("menu-follow"),
+            Self::CopyTle => t!("menu-copy_tle"),
+            Self::ShowPasses => t!("menu-show_passes"),
+            Self::OpenDetails => t!("menu-open_details"),
+        }
+        .into_owned()
+    }
+}
+
+impl Widget for ContextMenu<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(object) = &self.state.object else {
+            return;
+        };
+
+        let title = object.name().unwrap_or("UNK").to_string();
+        let labels: Vec<_> = Action::ALL.iter().map(|action| action.label()).collect();
+
+        const BORDER_WIDTH: u16 = 1;
+        let inner_width = labels
+            .iter()
+            .map(|label| label.width())
+            .chain([title.width()])
+            .max()
+            .unwrap() as u16;
+        let width = (inner_width + BORDER_WIDTH * 2).min(area.width);
+        let height = (labels.len() as u16 + BORDER_WIDTH * 2).min(area.height);
+        // Open the menu at the cursor, shifting it to stay within the area
+        let popup_area = Rect::new(
+            self.state
+                .position
+                .x
+                .min(area.right().saturating_sub(width)),
+            self.state
+                .position
+                .y
+                .min(area.bottom().saturating_sub(height)),
+            width,
+            height,
+        );
+
+        let block = Block::bordered().title(title.blue());
+        self.state.inner_area = block.inner(popup_area);
+
+        Clear.render(popup_area, buf);
+        let items = labels.into_iter().map(ListItem::new);
+        StatefulWidget::render(
+            List::new(items)
+                .block(block)
+                .highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            popup_area,
+            buf,
+            &mut self.state.list_state,
+        );
+    }
+}
+
+pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+    match event {
+        Event::Key(event) => handle_key_event(event, states),
+        Event::Mouse(event) => handle_mouse_event(event, states),
+        _ => Ok(()),
+    }
+}
+
+fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<()> {
+    let state = &mut states.context_menu_state;
+    match event.code {
+        KeyCode::Up | KeyCode::Char('k') => state.select_previous(),
+        KeyCode::Down | KeyCode::Char('j') => state.select_next(),
+        KeyCode::Enter => {
+            if let Some(index) = state.list_state.selected() {
+                return run_action(Action::ALL[index], states);
+            }
+        }
+        KeyCode::Esc => state.close(),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
+    let state = &mut states.context_menu_state;
+
+    let global_mouse = Position::new(event.column, event.row);
+    let Some(local_mouse) = window_to_area(global_mouse, state.inner_area) else {
+        // Close the menu when clicking outside of it
+        if let MouseEventKind::Down(_) = event.kind {
+            state.close();
+        }
+        return Ok(());
+    };
+
+    let index = local_mouse.y as usize;
+    match event.kind {
+        MouseEventKind::Moved => state.list_state.select(Some(index)),
+        MouseEventKind::Down(MouseButton::Left) => return run_action(Action::ALL[index], states),
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Runs the action on the object of the menu and closes the menu.
+fn run_action(action: Action, states: &mut States) -> Result<()> {
+    let Some(object) = states.context_menu_state.object.take() else {
+        return Ok(());
+    };
+
+    match action {
+        Action::Follow => {
+            states.shared.selected_object = Some(object);
+            states.world_map_state.enable_follow();
+        }
+        Action::CopyTle => information::copy_elements(&object, states)?,
+        Action::ShowPasses => {
+            states.shared.selected_object = Some(object);
+            states.tab_state.selected = Tab::Sky;
+        }
+        Action::OpenDetails => information::open_details(&object, states),
+    }
+    Ok(())
+}
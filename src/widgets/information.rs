@@ -1,8 +1,17 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    process::{Child, Command, Stdio},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+};
 
 use anyhow::Result;
 use arboard::Clipboard;
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     prelude::*,
     style::palette::tailwind,
@@ -15,13 +24,27 @@ use rust_i18n::t;
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::States, event::Event, object::Object, shared_state::SharedState, widgets::window_to_area,
+    app::States,
+    config::InformationConfig,
+    coordinates::wgs84,
+    event::Event,
+    format::{
+        format_declination, format_distance, format_number, format_period, format_right_ascension,
+        format_speed,
+    },
+    object::{self, Object},
+    shared_state::SharedState,
+    tle::format_tle,
+    utils::{COMPASS_POINTS, calculate_closest_approach, central_angle, compass_point},
+    widgets::{border_style, window_to_area},
 };
 
 /// A widget that displays information about a selected object.
 pub struct Information<'a> {
     pub state: &'a mut InformationState,
     pub shared: &'a SharedState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
 }
 
 /// State of a [`Information`] widget.
@@ -32,11 +55,84 @@ pub struct InformationState {
     table_entries: Vec<(String, String)>,
     /// The current state of the table widget.
     table_state: TableState,
+    /// URL template of the object details page, where `{norad_id}` is
+    /// replaced by the NORAD ID of the object.
+    details_url: String,
+    /// Whether to display the advanced fields.
+    show_advanced: bool,
+    /// Whether to display the geocentric latitude instead of the geodetic
+    /// latitude.
+    geocentric_latitude: bool,
+    /// How far ahead of the current time the closest approach is searched.
+    close_approach_window: Duration,
+    /// Distance in km below which the closest approach is flagged.
+    close_approach_threshold_km: f64,
+    /// The closest approach between the selected and reference objects.
+    closest_approach: Option<ClosestApproach>,
+    /// Whether the browser opened in the background exited with a failure.
+    open_url_failed: Arc<AtomicBool>,
     /// The inner rendering area of the widget.
     inner_area: Rect,
 }
 
 impl InformationState {
+    /// Creates a new `InformationState` with the given configuration.
+    pub fn with_config(config: InformationConfig) -> Self {
+        Self {
+            details_url: config.details_url,
+            show_advanced: config.show_advanced,
+            geocentric_latitude: config.geocentric_latitude,
+            close_approach_window: Duration::hours(config.close_approach_hours),
+            close_approach_threshold_km: config.close_approach_threshold_km,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the URL of the details page for the given object.
+    fn details_url(&self, object: &Object) -> String {
+        self.details_url
+            .replace("{norad_id}", &object.elements().norad_id.to_string())
+    }
+
+    /// Returns the closest approach between the objects, recomputing it if
+    /// the objects changed, or once per minute of simulated time.
+    fn closest_approach(
+        &mut self,
+        object: &Object,
+        reference: &Object,
+        time: DateTime<Utc>,
+    ) -> Option<(DateTime<Utc>, f64)> {
+        const UPDATE_INTERVAL: Duration = Duration::minutes(1);
+
+        let norad_ids = (object.elements().norad_id, reference.elements().norad_id);
+        if let Some(cache) = &self.closest_approach
+            && cache.norad_ids == norad_ids
+            && (time - cache.base_time).abs() < UPDATE_INTERVAL
+        {
+            return cache.result;
+        }
+
+        let result = calculate_closest_approach(
+            object,
+            reference,
+            &time,
+            &(time + self.close_approach_window),
+        );
+        self.closest_approach = Some(ClosestApproach {
+            norad_ids,
+            base_time: time,
+            result,
+        });
+        result
+    }
+
+    /// Returns whether the widget is narrow enough to display compact
+    /// numbers.
+    fn is_compact(&self) -> bool {
+        const COMPACT_WIDTH: u16 = 40;
+        self.inner_area.width < COMPACT_WIDTH
+    }
+
     fn scroll_up(&mut self) {
         *self.table_state.offset_mut() = self.table_state.offset().saturating_sub(1);
     }
@@ -52,9 +148,19 @@ impl InformationState {
     }
 }
 
+/// A cached closest approach between two objects.
+struct ClosestApproach {
+    /// The NORAD IDs of the objects.
+    norad_ids: (u64, u64),
+    /// The time the closest approach was computed at.
+    base_time: DateTime<Utc>,
+    /// The time of the closest approach and the distance in km.
+    result: Option<(DateTime<Utc>, f64)>,
+}
+
 impl Widget for Information<'_> {
     fn render(mut self, area: Rect, buf: &mut Buffer) {
-        let block = Self::block();
+        let block = self.block();
         self.state.inner_area = block.inner(area);
         block.render(area, buf);
 
@@ -69,8 +175,10 @@ impl Widget for Information<'_> {
 }
 
 impl Information<'_> {
-    fn block() -> Block<'static> {
-        Block::new().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+    fn block(&self) -> Block<'static> {
+        Block::new()
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_style(border_style(self.focused))
     }
 
     fn table(&mut self, object: &Object) -> Table<'static> {
@@ -134,11 +242,45 @@ impl Information<'_> {
         );
     }
 
+    /// Formats the beta angle of the object, flagging orbits without eclipses.
+    fn beta_angle(&self, object: &Object) -> String {
+        let beta_angle = object.beta_angle(&self.shared.time.time()).unwrap();
+        let mut text = format!("{}°", format_number(beta_angle, Some(1)));
+        if object.is_full_sun(beta_angle) {
+            text += &format!(" ({})", t!("info-full_sun"));
+        }
+        text
+    }
+
+    /// Formats the direction of travel of the object as a compass direction.
+    fn heading(&self, object: &Object) -> String {
+        let heading = object.heading(&self.shared.time.time()).unwrap();
+        format!(
+            "{} ({}°)",
+            COMPASS_POINTS[compass_point(heading)],
+            format_number(heading, Some(0))
+        )
+    }
+
+    /// Returns the latitude entry, clearly labeled if it is geocentric.
+    fn latitude(&self, state: &object::State) -> (String, String) {
+        let (key, latitude) = if self.state.geocentric_latitude {
+            (t!("info-geocentric_latitude"), state.geocentric_latitude())
+        } else {
+            (t!("info-latitude"), state.latitude())
+        };
+        (
+            key.into(),
+            format!("{:>9}°", format_number(latitude, Some(4))),
+        )
+    }
+
     fn update_table_entries(&mut self, object: &Object) {
         const UNKNOWN: &str = "(Unknown)";
 
         let state = object.predict(&self.shared.time.time()).unwrap();
         let (country, city) = state.position.country_city();
+        let compact = self.state.is_compact();
         let elements = object.elements();
         self.state.table_entries = vec![
             (
@@ -155,59 +297,185 @@ impl Information<'_> {
             ),
             (t!("info-norad_id").into(), elements.norad_id.to_string()),
             (
-                t!("info-longitude").into(),
-                format!("{:9.4}°", state.longitude()),
+                t!("info-group").into(),
+                object.group().unwrap_or(UNKNOWN).into(),
             ),
             (
-                t!("info-latitude").into(),
-                format!("{:9.4}°", state.latitude()),
+                t!("info-longitude").into(),
+                format!("{:>9}°", format_number(state.longitude(), Some(4))),
             ),
+            self.latitude(&state),
             (
                 t!("info-altitude").into(),
-                format!("{:8.3} km", state.altitude()),
+                format_distance(state.altitude(), compact),
             ),
             (
                 t!("info-speed").into(),
-                format!("{:.2} km/s", state.speed()),
+                format_speed(state.speed(), compact),
+            ),
+            (
+                t!("info-ground_speed").into(),
+                format_speed(
+                    object.ground_speed(&self.shared.time.time()).unwrap(),
+                    compact,
+                ),
             ),
+            (t!("info-heading").into(), self.heading(object)),
             (
                 t!("info-period").into(),
-                format!("{:.2} min", object.orbital_period().as_seconds_f64() / 60.0),
+                format_period(*object.orbital_period(), compact),
+            ),
+            (
+                t!("info-nodal_period").into(),
+                format_period(object.nodal_period(), compact),
             ),
+            (t!("info-repeat_cycle").into(), repeat_cycle(object)),
+            (t!("info-beta_angle").into(), self.beta_angle(object)),
             (t!("info-location").into(), format!("{city}, {country}")),
             (
                 t!("info-epoch").into(),
-                object.epoch().format("%Y-%m-%d %H:%M:%S").to_string(),
+                self.shared.format_time(&object.epoch()),
             ),
             (
                 t!("info-drag_term").into(),
-                format!("{} 1/ER", elements.drag_term),
+                format!("{} 1/ER", format_number(elements.drag_term, None)),
             ),
             (
                 t!("info-inclination").into(),
-                format!("{}°", elements.inclination),
+                format!("{}°", format_number(elements.inclination, None)),
             ),
             (
                 t!("info-right_ascension").into(),
-                format!("{}°", elements.right_ascension),
+                format!("{}°", format_number(elements.right_ascension, None)),
             ),
             (
                 t!("info-eccentricity").into(),
-                elements.eccentricity.to_string(),
+                format_number(elements.eccentricity, None),
             ),
             (
                 t!("info-mean_anomaly").into(),
-                format!("{}°", elements.mean_anomaly),
+                format!("{}°", format_number(elements.mean_anomaly, None)),
             ),
             (
                 t!("info-mean_motion").into(),
-                format!("{} 1/day", elements.mean_motion),
+                format!("{} 1/day", format_number(elements.mean_motion, None)),
             ),
             (
                 t!("info-rev_num").into(),
                 elements.revolution_number.to_string(),
             ),
         ];
+
+        // Match the position against star charts while the object is visible
+        if let Some(station) = &self.shared.ground_station
+            && state.position.az_el(&station.position).1 >= 0.0
+        {
+            let (ra, dec) = state.ra_dec(&station.position, &self.shared.time.time());
+            self.state.table_entries.extend([
+                (
+                    t!("info-right_ascension_topo").into(),
+                    format_right_ascension(ra),
+                ),
+                (t!("info-declination_topo").into(), format_declination(dec)),
+            ]);
+        }
+
+        if let Some(reference) = &self.shared.reference_object {
+            let entries = self.comparison_entries(object, &state, reference);
+            self.state.table_entries.extend(entries);
+        }
+
+        if self.state.show_advanced {
+            let time = self.shared.time.time();
+            let angle = |angle: f64| format!("{}°", format_number(angle, Some(4)));
+            self.state.table_entries.extend([
+                (
+                    t!("info-current_mean_anomaly").into(),
+                    angle(object.mean_anomaly(&time)),
+                ),
+                (
+                    t!("info-eccentric_anomaly").into(),
+                    angle(object.eccentric_anomaly(&time)),
+                ),
+                (
+                    t!("info-true_anomaly").into(),
+                    angle(object.true_anomaly(&time)),
+                ),
+            ]);
+        }
+    }
+
+    /// Returns the entries comparing the reference object with the selected
+    /// object, whose state is given.
+    fn comparison_entries(
+        &mut self,
+        object: &Object,
+        state: &object::State,
+        reference: &Object,
+    ) -> Vec<(String, String)> {
+        let time = self.shared.time.time();
+        let compact = self.state.is_compact();
+        let reference_state = reference.predict(&time).unwrap();
+        let ground_distance = central_angle(
+            (state.longitude(), state.latitude()),
+            (reference_state.longitude(), reference_state.latitude()),
+        ) * wgs84::MEAN_RADIUS;
+        vec![
+            (
+                t!("info-reference").into(),
+                reference.name().unwrap_or("UNK").into(),
+            ),
+            (
+                t!("info-reference_altitude").into(),
+                format_distance(reference_state.altitude(), compact),
+            ),
+            (
+                t!("info-reference_period").into(),
+                format_period(*reference.orbital_period(), compact),
+            ),
+            (
+                t!("info-reference_inclination").into(),
+                format!("{}°", format_number(reference.elements().inclination, None)),
+            ),
+            (
+                t!("info-separation").into(),
+                format_distance(state.position.range(&reference_state.position), compact),
+            ),
+            (
+                t!("info-ground_distance").into(),
+                format_distance(ground_distance, compact),
+            ),
+            (
+                t!("info-closest_approach").into(),
+                self.closest_approach(object, reference, time),
+            ),
+        ]
+    }
+
+    /// Formats the closest approach between the objects, flagging close
+    /// approaches.
+    fn closest_approach(
+        &mut self,
+        object: &Object,
+        reference: &Object,
+        time: DateTime<Utc>,
+    ) -> String {
+        if object.elements().norad_id == reference.elements().norad_id {
+            return "-".into();
+        }
+        let Some((approach_time, distance)) = self.state.closest_approach(object, reference, time)
+        else {
+            return "-".into();
+        };
+        let mut text = format!(
+            "{}, {}",
+            format_distance(distance, self.state.is_compact()),
+            self.shared.format_time(&approach_time)
+        );
+        if distance < self.state.close_approach_threshold_km {
+            text += &format!(" ({})", t!("info-close_approach"));
+        }
+        text
     }
 
     fn centered_paragraph<'a>(text: impl Into<Text<'a>>) -> Paragraph<'a> {
@@ -215,13 +483,111 @@ impl Information<'_> {
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
-        Event::Mouse(event) => handle_mouse_event(event, states),
-        _ => Ok(()),
+        Event::Key(event) => handle_key_event(event, states),
+        Event::Mouse(event) => handle_mouse_event(event, states).map(|()| false),
+        Event::Update => {
+            if states
+                .information_state
+                .open_url_failed
+                .swap(false, Ordering::Relaxed)
+            {
+                states.toast_state.show(t!("toast-open_url_failed"));
+            }
+            Ok(false)
+        }
+        _ => Ok(false),
     }
 }
 
+fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<bool> {
+    match event.code {
+        // Toggle the advanced fields.
+        KeyCode::Char('a') => {
+            states.information_state.show_advanced = !states.information_state.show_advanced;
+            return Ok(true);
+        }
+        // Toggle between the geodetic and geocentric latitude.
+        KeyCode::Char('G') => {
+            states.information_state.geocentric_latitude =
+                !states.information_state.geocentric_latitude;
+            return Ok(true);
+        }
+        KeyCode::Char('o' | 'y' | 'R') => {}
+        _ => return Ok(false),
+    }
+
+    // The other keys act on the selected object
+    let Some(object) = states.shared.selected_object.clone() else {
+        return Ok(true);
+    };
+
+    match event.code {
+        KeyCode::Char('o') => open_details(&object, states),
+        KeyCode::Char('y') => copy_elements(&object, states)?,
+        KeyCode::Char('R') => toggle_reference(object, states),
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Opens the details page of the object in the browser.
+pub fn open_details(object: &Object, states: &mut States) {
+    let url = states.information_state.details_url(object);
+    match open_url(&url) {
+        Ok(mut child) => {
+            // Reap the process in the background, reporting a failure on the
+            // next update.
+            let failed = Arc::clone(&states.information_state.open_url_failed);
+            thread::spawn(move || {
+                if !child.wait().is_ok_and(|status| status.success()) {
+                    failed.store(true, Ordering::Relaxed);
+                }
+            });
+        }
+        Err(_) => states.toast_state.show(t!("toast-open_url_failed")),
+    }
+}
+
+/// Sets the object as the reference object, or clears the reference if it is
+/// already the object.
+fn toggle_reference(object: Object, states: &mut States) {
+    let norad_id = object.elements().norad_id;
+    let reference = &mut states.shared.reference_object;
+    if reference
+        .as_ref()
+        .is_some_and(|reference| reference.elements().norad_id == norad_id)
+    {
+        *reference = None;
+        states.toast_state.show(t!("toast-reference_cleared"));
+    } else {
+        let name = object.name().unwrap_or("UNK").to_owned();
+        *reference = Some(object);
+        states
+            .toast_state
+            .show(t!("toast-reference_set", name = name));
+    }
+}
+
+/// Copies the TLE of the object to the clipboard, or the JSON elements if they
+/// cannot be formatted as a TLE.
+pub fn copy_elements(object: &Object, states: &mut States) -> Result<()> {
+    let (text, message) = match format_tle(object.elements()) {
+        Some(tle) => (tle, t!("toast-tle_copied")),
+        None => (
+            serde_json::to_string_pretty(object.elements())?,
+            t!("toast-elements_copied"),
+        ),
+    };
+    match Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text)) {
+        Ok(()) => states.toast_state.show(message),
+        Err(_) => states.toast_state.show(t!("toast-copy_failed")),
+    }
+    Ok(())
+}
+
 fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     let state = &mut states.information_state;
 
@@ -259,6 +625,19 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     Ok(())
 }
 
+/// Formats the repeat cycle of the ground track of the object.
+fn repeat_cycle(object: &Object) -> String {
+    match object.repeat_cycle() {
+        Some(cycle) => t!(
+            "info-repeat_cycle_value",
+            days = cycle.days,
+            orbits = cycle.orbits
+        )
+        .into_owned(),
+        None => t!("info-non_repeating").into_owned(),
+    }
+}
+
 /// Truncates a string to fit within the specified width, adding an ellipsis if
 /// necessary.
 fn truncate<'a>(str: &'a str, max_width: usize) -> Cow<'a, str> {
@@ -277,3 +656,25 @@ fn truncate<'a>(str: &'a str, max_width: usize) -> Cow<'a, str> {
         Cow::Owned(format!("{}{}", &str[..end], ELLIPSIS))
     }
 }
+
+/// Opens the URL in the default browser, returning the spawned process.
+fn open_url(url: &str) -> std::io::Result<Child> {
+    // `cmd /C start` would split the URL at `&`, so hand it to the URL
+    // protocol handler directly
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
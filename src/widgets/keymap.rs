@@ -5,34 +5,110 @@ use ratatui::{
 use rust_i18n::t;
 use unicode_width::UnicodeWidthStr;
 
+use crate::widgets::centered_rect;
+
 // Global
 const GLOBAL_BINDINGS: &[(&str, &str)] = &[
     ("q, <C-c>", "keymap-quit"),
     ("?", "keymap-help"),
     ("<Esc>", "keymap-dismiss"),
     ("<Tab> / <S-Tab>", "keymap-switch_tab"),
+    ("w / W", "keymap-focus"),
+    ("b", "keymap-blink"),
+    ("L", "keymap-log"),
+    ("O", "keymap-objects_table"),
+    ("D", "keymap-about"),
+    ("p", "keymap-dump_frame"),
+    ("C", "keymap-clean_mode"),
+    ("<C-r>", "keymap-reload_config"),
+    ("1-9", "keymap-recall_preset"),
+    ("<A-1>-<A-9>", "keymap-save_preset"),
 ];
 
 // World Map
 const MAP_BINDINGS: &[(&str, &str)] = &[
     ("<LeftMouse>", "keymap-select"),
+    ("<C-LeftMouse> + drag", "keymap-select_rectangle"),
     ("<RightMouse>", "keymap-deselect"),
     ("<ScrollWheelUp> / <ScrollWheelDown>", "keymap-map_move"),
     ("[ / ]", "keymap-map_move"),
     ("f", "keymap-follow"),
+    ("+ / -", "keymap-follow_smoothing"),
     ("t", "keymap-terminator"),
+    ("T", "keymap-twilight"),
+    ("n / N", "keymap-center_sun"),
+    ("v", "keymap-visibility_area"),
+    ("e", "keymap-shadow_track"),
+    ("i", "keymap-inclination_band"),
+    ("c", "keymap-fill_land"),
+    ("h", "keymap-highlight"),
+    ("l", "keymap-labels"),
+    ("x", "keymap-filter"),
+    ("V", "keymap-elevation_filter"),
+    ("I", "keymap-inertial_track"),
+    ("A", "keymap-altitude_bands"),
+];
+
+// Information
+const INFORMATION_BINDINGS: &[(&str, &str)] = &[
+    ("o", "keymap-open_details"),
+    ("y", "keymap-copy_tle"),
+    ("a", "keymap-advanced_fields"),
+    ("G", "keymap-geocentric_latitude"),
+    ("R", "keymap-reference_object"),
+];
+
+// Sky
+const SKY_BINDINGS: &[(&str, &str)] =
+    &[("P", "keymap-elevation_plot"), ("U", "keymap-sky_rotation")];
+
+// Satellite Groups
+const SATELLITE_GROUPS_BINDINGS: &[(&str, &str)] = &[
+    ("<LeftMouse>", "keymap-toggle_group"),
+    ("F", "keymap-toggle_favorite"),
+    ("g", "keymap-next_favorite"),
+    ("u", "keymap-refresh_group"),
+    ("s", "keymap-group_order"),
+    ("S", "keymap-object_order"),
 ];
 
 // Timeline
 const TIMELINE_BINDINGS: &[(&str, &str)] = &[
+    ("<LeftMouse>", "keymap-set_time"),
+    ("<A-LeftMouse>", "keymap-set_time_free"),
     ("<ScrollWheelUp> / <ScrollWheelDown>", "keymap-adjust_time"),
+    ("<Left> / <Right>, , / .", "keymap-adjust_time"),
+    ("<S-Left> / <S-Right>", "keymap-adjust_time_hour"),
+    ("E", "keymap-epoch_time"),
+    ("z", "keymap-freeze_time"),
+    ("m", "keymap-min_pass_elevation"),
     ("r", "keymap-reset_time"),
 ];
 
+// Objects Table
+const OBJECTS_TABLE_BINDINGS: &[(&str, &str)] = &[
+    ("<LeftMouse>", "keymap-objects_select"),
+    ("<Up> / <Down>, k / j", "keymap-objects_move"),
+    ("s", "keymap-sort_column"),
+    ("S", "keymap-sort_order"),
+];
+
+// Context Menu
+const CONTEXT_MENU_BINDINGS: &[(&str, &str)] = &[
+    ("<LeftMouse>, <Enter>", "keymap-menu_run"),
+    ("<Up> / <Down>, k / j", "keymap-menu_move"),
+    ("<Esc>", "keymap-menu_close"),
+];
+
 const SECTIONS: &[(&str, &[(&str, &str)])] = &[
     ("keymap-global", GLOBAL_BINDINGS),
     ("keymap-world_map", MAP_BINDINGS),
+    ("keymap-information", INFORMATION_BINDINGS),
+    ("keymap-sky", SKY_BINDINGS),
+    ("keymap-satellite_groups", SATELLITE_GROUPS_BINDINGS),
     ("keymap-timeline", TIMELINE_BINDINGS),
+    ("keymap-objects", OBJECTS_TABLE_BINDINGS),
+    ("keymap-context_menu", CONTEXT_MENU_BINDINGS),
 ];
 
 pub struct Keymap;
@@ -107,9 +183,3 @@ impl Widget for Keymap {
             .render(popup_area, buf);
     }
 }
-
-fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
-    let x = area.x + area.width.saturating_sub(width) / 2;
-    let y = area.y + area.height.saturating_sub(height) / 2;
-    Rect::new(x, y, width.min(area.width), height.min(area.height))
-}
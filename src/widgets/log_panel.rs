@@ -0,0 +1,45 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph},
+};
+use rust_i18n::t;
+
+use crate::log;
+
+/// A widget that displays recent log entries.
+pub struct LogPanel;
+
+impl LogPanel {
+    fn block() -> Block<'static> {
+        Block::bordered().title(t!("log-title").to_string().blue())
+    }
+}
+
+impl Widget for LogPanel {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let height = (area.height / 2).max(3);
+        let popup_area = Rect {
+            y: area.y + area.height.saturating_sub(height),
+            height: height.min(area.height),
+            ..area
+        };
+
+        let block = Self::block();
+        let count = block.inner(popup_area).height as usize;
+        let lines: Vec<_> = log::last(count)
+            .into_iter()
+            .map(|entry| {
+                Line::from(vec![
+                    Span::styled(
+                        entry.time.format("%H:%M:%S ").to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(entry.message),
+                ])
+            })
+            .collect();
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(lines).block(block).render(popup_area, buf);
+    }
+}
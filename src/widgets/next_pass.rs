@@ -0,0 +1,133 @@
+use chrono::{DateTime, Duration, Utc};
+use ratatui::prelude::*;
+use rayon::prelude::*;
+use rust_i18n::t;
+
+use crate::{
+    app::States,
+    coordinates::Lla,
+    event::Event,
+    format::format_number,
+    object::Object,
+    shared_state::SharedState,
+    utils::{Pass, calculate_pass_times},
+};
+
+/// A status line that displays the next pass over the ground station among
+/// the favorite and selected objects.
+pub struct NextPass<'a> {
+    pub state: &'a NextPassState,
+    pub shared: &'a SharedState,
+}
+
+/// State of a [`NextPass`] widget.
+#[derive(Default)]
+pub struct NextPassState {
+    /// The name of the object and its next pass.
+    next_pass: Option<(String, Pass)>,
+    /// The NORAD IDs of the watched objects, the station position and the
+    /// minimum pass elevation.
+    key: Option<(Vec<u64>, Lla, f64)>,
+    /// The time the next pass was computed at.
+    base_time: DateTime<Utc>,
+}
+
+impl NextPassState {
+    /// How far ahead of the current time passes are searched.
+    const LOOK_AHEAD: Duration = Duration::hours(24);
+    /// The interval at which the next pass is recomputed.
+    const UPDATE_INTERVAL: Duration = Duration::minutes(1);
+
+    /// Recomputes the next pass if the watched objects or the station changed,
+    /// or once per minute of simulated time.
+    fn update(&mut self, shared: &SharedState) {
+        let time = shared.time.time();
+        let Some(station) = &shared.ground_station else {
+            self.key = None;
+            self.next_pass = None;
+            return;
+        };
+
+        let objects = watched_objects(shared);
+        let key = (
+            objects
+                .iter()
+                .map(|object| object.elements().norad_id)
+                .collect(),
+            station.position.clone(),
+            shared.pass_cache.min_elevation(),
+        );
+        if self.key.as_ref() == Some(&key) && (time - self.base_time).abs() < Self::UPDATE_INTERVAL
+        {
+            return;
+        }
+
+        // Include the ongoing passes, and break ties by NORAD ID
+        let min_elevation = shared.pass_cache.min_elevation();
+        self.next_pass = objects
+            .par_iter()
+            .filter_map(|object| {
+                let pass = calculate_pass_times(
+                    object,
+                    &station.position,
+                    &time,
+                    &(time + Self::LOOK_AHEAD),
+                )
+                .into_iter()
+                .find(|pass| pass.max_elevation >= min_elevation)?;
+                Some((pass.aos, object.elements().norad_id, object, pass))
+            })
+            .min_by_key(|(aos, norad_id, _, _)| (*aos, *norad_id))
+            .map(|(_, _, object, pass)| (object.name().unwrap_or("UNK").to_owned(), pass));
+        self.key = Some(key);
+        self.base_time = time;
+    }
+}
+
+/// Returns the favorite objects and the selected object, ordered by NORAD ID
+/// without duplicates.
+fn watched_objects(shared: &SharedState) -> Vec<&Object> {
+    let mut objects: Vec<_> = shared
+        .objects
+        .iter()
+        .filter(|object| shared.favorites.contains(&object.elements().norad_id))
+        .chain(shared.selected_object.as_ref())
+        .collect();
+    objects.sort_by_key(|object| object.elements().norad_id);
+    objects.dedup_by_key(|object| object.elements().norad_id);
+    objects
+}
+
+impl Widget for NextPass<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some((name, pass)) = &self.state.next_pass else {
+            return;
+        };
+
+        let time = self.shared.time.time();
+        if pass.los < time {
+            return;
+        }
+        let elevation = format_number(pass.max_elevation, Some(0));
+        let text = if pass.aos <= time {
+            t!("next_pass-ongoing", name = name, elevation = elevation)
+        } else {
+            t!(
+                "next_pass-upcoming",
+                name = name,
+                minutes = (pass.aos - time).num_minutes(),
+                elevation = elevation
+            )
+        };
+        Line::from(format!(" {text} "))
+            .light_yellow()
+            .right_aligned()
+            .render(area, buf);
+    }
+}
+
+pub fn handle_event(event: Event, states: &mut States) {
+    if let Event::Update = event {
+        states.next_pass_state.update(&states.shared);
+    }
+}
@@ -0,0 +1,438 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Cell, Clear, Row, StatefulWidget, Table, TableState},
+};
+use rayon::prelude::*;
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    app::States,
+    coordinates::Lla,
+    event::Event,
+    format::format_number,
+    shared_state::SharedState,
+    utils::calculate_pass_times,
+    widgets::{timeline::TimelineState, window_to_area, world_map::WorldMapState},
+};
+
+/// A popup table listing the loaded objects that pass the filters of the world
+/// map, sortable by any column.
+pub struct ObjectsTable<'a> {
+    pub state: &'a mut ObjectsTableState,
+    pub shared: &'a SharedState,
+    /// The world map state, whose filters the objects must pass.
+    pub world_map_state: &'a WorldMapState,
+    /// The timeline state, whose time zones the next passes are displayed in.
+    pub timeline_state: &'a TimelineState,
+}
+
+/// State of an [`ObjectsTable`] widget.
+#[derive(Default)]
+pub struct ObjectsTableState {
+    /// Whether the table is open.
+    open: bool,
+    /// The column the rows are sorted by.
+    sort_column: Column,
+    /// Whether the rows are sorted in descending order.
+    descending: bool,
+    /// The NORAD IDs of the rows, in display order.
+    row_ids: Vec<u64>,
+    /// The AOS of the next pass of each object, or `None` if there is no pass
+    /// in the look-ahead window.
+    next_passes: HashMap<u64, Option<DateTime<Utc>>>,
+    /// The NORAD IDs of the objects, the station position and the minimum
+    /// pass elevation the next passes were computed for.
+    pass_key: Option<(Vec<u64>, Lla, f64)>,
+    /// The time the next passes were computed at.
+    base_time: DateTime<Utc>,
+    /// The current state of the table widget.
+    table_state: TableState,
+    /// The inner rendering area of the widget.
+    inner_area: Rect,
+}
+
+impl ObjectsTableState {
+    /// The maximum number of objects whose next pass is computed.
+    const MAX_PASS_OBJECTS: usize = 200;
+    /// How far ahead of the current time passes are searched.
+    const LOOK_AHEAD: Duration = Duration::hours(24);
+    /// The interval at which the next passes are recomputed.
+    const UPDATE_INTERVAL: Duration = Duration::minutes(1);
+
+    /// Opens the table if it is closed, otherwise closes it.
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Closes the table.
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    /// Returns whether the table is open.
+    pub fn is_open(&self) -> bool {
+        self.open
+    }
+
+    /// Recomputes the next passes if the objects or the station changed, or
+    /// once per minute of simulated time.
+    fn update_next_passes(&mut self, shared: &SharedState) {
+        let (Some(station), true) = (
+            &shared.ground_station,
+            shared.objects.len() <= Self::MAX_PASS_OBJECTS,
+        ) else {
+            self.pass_key = None;
+            self.next_passes.clear();
+            return;
+        };
+
+        let time = shared.time.time();
+        let min_elevation = shared.pass_cache.min_elevation();
+        let key = (
+            shared
+                .objects
+                .iter()
+                .map(|object| object.elements().norad_id)
+                .collect(),
+            station.position.clone(),
+            min_elevation,
+        );
+        if self.pass_key.as_ref() == Some(&key)
+            && (time - self.base_time).abs() < Self::UPDATE_INTERVAL
+        {
+            return;
+        }
+
+        self.next_passes = shared
+            .objects
+            .par_iter()
+            .map(|object| {
+                let aos = calculate_pass_times(
+                    object,
+                    &station.position,
+                    &time,
+                    &(time + Self::LOOK_AHEAD),
+                )
+                .into_iter()
+                .find(|pass| pass.max_elevation >= min_elevation)
+                .map(|pass| pass.aos);
+                (object.elements().norad_id, aos)
+            })
+            .collect();
+        self.pass_key = Some(key);
+        self.base_time = time;
+    }
+}
+
+/// A column of the objects table.
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+enum Column {
+    #[default]
+    Name,
+    NoradId,
+    Altitude,
+    Elevation,
+    NextPass,
+}
+
+impl Column {
+    const ALL: [Self; 5] = [
+        Self::Name,
+        Self::NoradId,
+        Self::Altitude,
+        Self::Elevation,
+        Self::NextPass,
+    ];
+
+    /// Returns the next column in the cycle.
+    fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    fn title(self) -> String {
+        match self {
+            Self::Name => t!("objects-name"),
+            Self::NoradId => t!("objects-norad_id"),
+            Self::Altitude => t!("objects-altitude"),
+            Self::Elevation => t!("objects-elevation"),
+            Self::NextPass => t!("objects-next_pass"),
+        }
+        .into_owned()
+    }
+}
+
+/// A row of the objects table.
+struct ObjectRow<'a> {
+    name: &'a str,
+    norad_id: u64,
+    altitude: f64,
+    /// Elevation above the ground station in degrees, if configured.
+    elevation: Option<f64>,
+    /// AOS of the next pass, if computed and found.
+    next_pass: Option<DateTime<Utc>>,
+}
+
+impl ObjectRow<'_> {
+    /// Compares the rows by the given column, listing missing values last in
+    /// both orders.
+    fn compare(&self, other: &Self, column: Column, descending: bool) -> Ordering {
+        fn order(ordering: Ordering, descending: bool) -> Ordering {
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        }
+        fn missing_last<T: PartialOrd>(a: Option<T>, b: Option<T>, descending: bool) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => {
+                    order(a.partial_cmp(&b).unwrap_or(Ordering::Equal), descending)
+                }
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+
+        match column {
+            Column::Name => order(self.name.cmp(other.name), descending),
+            Column::NoradId => order(self.norad_id.cmp(&other.norad_id), descending),
+            Column::Altitude => order(self.altitude.total_cmp(&other.altitude), descending),
+            Column::Elevation => missing_last(self.elevation, other.elevation, descending),
+            Column::NextPass => missing_last(self.next_pass, other.next_pass, descending),
+        }
+    }
+}
+
+impl Widget for ObjectsTable<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let time = self.shared.time.time();
+        let station = self.shared.ground_station.as_ref();
+        let mut rows: Vec<_> = self
+            .shared
+            .objects
+            .par_iter()
+            .filter_map(|object| {
+                let state = object.predict(&time).unwrap();
+                if !self.world_map_state.is_visible(object, &state, station) {
+                    return None;
+                }
+                let norad_id = object.elements().norad_id;
+                Some(ObjectRow {
+                    name: object.name().unwrap_or("UNK"),
+                    norad_id,
+                    altitude: state.altitude(),
+                    elevation: self
+                        .shared
+                        .ground_station
+                        .as_ref()
+                        .map(|station| state.position.az_el(&station.position).1),
+                    next_pass: self.state.next_passes.get(&norad_id).copied().flatten(),
+                })
+            })
+            .collect();
+        let (column, descending) = (self.state.sort_column, self.state.descending);
+        rows.sort_by(|a, b| a.compare(b, column, descending));
+        self.state.row_ids = rows.iter().map(|row| row.norad_id).collect();
+
+        // Highlight the selected object
+        let selected_id = self
+            .shared
+            .selected_object
+            .as_ref()
+            .map(|object| object.elements().norad_id);
+        self.state.table_state.select(
+            selected_id.and_then(|id| self.state.row_ids.iter().position(|&row| row == id)),
+        );
+
+        let header = Row::new(Column::ALL.map(|column| {
+            let mut title = column.title();
+            if column == self.state.sort_column {
+                title += if self.state.descending {
+                    " ▼"
+                } else {
+                    " ▲"
+                };
+            }
+            Cell::from(title.bold())
+        }))
+        .cyan();
+        let passes_computed = self.shared.ground_station.is_some()
+            && self.shared.objects.len() <= ObjectsTableState::MAX_PASS_OBJECTS;
+        let table_rows: Vec<_> = rows
+            .iter()
+            .map(|row| {
+                let elevation = row.elevation.map_or("-".into(), |elevation| {
+                    format!("{}°", format_number(elevation, Some(1)))
+                });
+                let next_pass = match row.next_pass {
+                    _ if !passes_computed => "-".into(),
+                    Some(aos) if aos <= time => t!("objects-in_pass").into_owned(),
+                    Some(aos) => self.timeline_state.format_time_of_day(self.shared, aos),
+                    None => t!("objects-no_pass").into_owned(),
+                };
+                let style = match row.elevation {
+                    Some(elevation) if elevation >= 0.0 => Style::new().light_green(),
+                    _ => Style::new(),
+                };
+                (
+                    [
+                        row.name.to_owned(),
+                        row.norad_id.to_string(),
+                        format!("{} km", format_number(row.altitude, Some(0))),
+                        elevation,
+                        next_pass,
+                    ],
+                    style,
+                )
+            })
+            .collect();
+        // Fit the next passes, whose width depends on the time format
+        let next_pass_width = table_rows
+            .iter()
+            .map(|(cells, _)| cells[4].width())
+            .max()
+            .unwrap_or(0)
+            .max(11);
+        let widths = [
+            Constraint::Fill(1),
+            Constraint::Length(10),
+            Constraint::Length(12),
+            Constraint::Length(11),
+            Constraint::Length(next_pass_width as u16),
+        ];
+        let table_rows = table_rows
+            .into_iter()
+            .map(|(cells, style)| Row::new(cells).style(style));
+
+        let mut block =
+            Block::bordered().title(t!("objects-title", count = rows.len()).to_string().blue());
+        if self.shared.ground_station.is_some() && !passes_computed {
+            let label = t!(
+                "objects-passes_limited",
+                max = ObjectsTableState::MAX_PASS_OBJECTS
+            );
+            block = block.title_bottom(Line::from(format!("({label})")).dark_gray());
+        }
+        // Keep the header out of the hit-testing area
+        self.state.inner_area = block.inner(area);
+        self.state.inner_area.y += 1;
+        self.state.inner_area.height = self.state.inner_area.height.saturating_sub(1);
+
+        Clear.render(area, buf);
+        StatefulWidget::render(
+            Table::new(table_rows, widths)
+                .header(header)
+                .block(block)
+                .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED)),
+            area,
+            buf,
+            &mut self.state.table_state,
+        );
+    }
+}
+
+pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+    match event {
+        Event::Update if states.objects_table_state.is_open() => {
+            states
+                .objects_table_state
+                .update_next_passes(&states.shared);
+        }
+        Event::Key(event) => handle_key_event(event, states),
+        Event::Mouse(event) => handle_mouse_event(event, states),
+        _ => {}
+    }
+    Ok(())
+}
+
+fn handle_key_event(event: KeyEvent, states: &mut States) {
+    let state = &mut states.objects_table_state;
+    match event.code {
+        KeyCode::Up | KeyCode::Char('k') => select_relative(-1, states),
+        KeyCode::Down | KeyCode::Char('j') => select_relative(1, states),
+        // Cycle the sort column.
+        KeyCode::Char('s') => state.sort_column = state.sort_column.next(),
+        // Reverse the sort order.
+        KeyCode::Char('S') => state.descending = !state.descending,
+        _ => {}
+    }
+}
+
+fn handle_mouse_event(event: MouseEvent, states: &mut States) {
+    let state = &states.objects_table_state;
+    let global_mouse = Position::new(event.column, event.row);
+    let Some(local_mouse) = window_to_area(global_mouse, state.inner_area) else {
+        return;
+    };
+
+    match event.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            let index = state.table_state.offset() + local_mouse.y as usize;
+            if let Some(&norad_id) = state.row_ids.get(index) {
+                select(norad_id, states);
+            }
+        }
+        MouseEventKind::ScrollUp => select_relative(-1, states),
+        MouseEventKind::ScrollDown => select_relative(1, states),
+        _ => {}
+    }
+}
+
+/// Selects the object of the row at the given offset from the row of the
+/// selected object, or the first row if none is selected.
+fn select_relative(offset: isize, states: &mut States) {
+    let row_ids = &states.objects_table_state.row_ids;
+    if row_ids.is_empty() {
+        return;
+    }
+    let index = match states.objects_table_state.table_state.selected() {
+        Some(index) => (index as isize + offset).clamp(0, row_ids.len() as isize - 1) as usize,
+        None => 0,
+    };
+    select(row_ids[index], states);
+}
+
+/// Selects the object with the given NORAD ID.
+fn select(norad_id: u64, states: &mut States) {
+    let Some(object) = states
+        .shared
+        .objects
+        .iter()
+        .find(|object| object.elements().norad_id == norad_id)
+    else {
+        return;
+    };
+    states
+        .world_map_state
+        .follow_on_select(object, &states.shared.time.time());
+    states.shared.selected_object = Some(object.clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_values_are_sorted_last() {
+        let row = |norad_id, elevation| ObjectRow {
+            name: "",
+            norad_id,
+            altitude: 0.0,
+            elevation,
+            next_pass: None,
+        };
+        let mut rows = [row(1, None), row(2, Some(10.0)), row(3, Some(-5.0))];
+        let ids = |rows: &[ObjectRow]| rows.iter().map(|row| row.norad_id).collect::<Vec<_>>();
+        rows.sort_by(|a, b| a.compare(b, Column::Elevation, false));
+        assert_eq!(ids(&rows), [3, 2, 1]);
+        rows.sort_by(|a, b| a.compare(b, Column::Elevation, true));
+        assert_eq!(ids(&rows), [2, 3, 1]);
+    }
+}
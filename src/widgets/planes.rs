@@ -0,0 +1,205 @@
+use anyhow::Result;
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::{
+    prelude::*,
+    widgets::{
+        Block, Borders, Paragraph, Wrap,
+        canvas::{Canvas, Points},
+    },
+};
+use rust_i18n::t;
+
+use crate::{
+    app::States,
+    event::Event,
+    format::format_number,
+    object::Object,
+    shared_state::SharedState,
+    widgets::{border_style, tabs::Tab, window_to_area},
+};
+
+/// A widget that plots the inclination against the right ascension of the
+/// ascending node (RAAN) of the loaded objects, grouping them by orbital
+/// plane.
+pub struct Planes<'a> {
+    pub state: &'a mut PlanesState,
+    pub shared: &'a SharedState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
+}
+
+/// State of a [`Planes`] widget.
+#[derive(Default)]
+pub struct PlanesState {
+    /// The plotted range of inclinations in degrees.
+    inclination_bounds: [f64; 2],
+    /// The inner rendering area of the widget.
+    inner_area: Rect,
+}
+
+impl PlanesState {
+    /// Margin in degrees added around the range of inclinations.
+    const INCLINATION_MARGIN: f64 = 1.0;
+    /// Maximum distance in cells between a click and the selected object.
+    const MAX_SELECTION_DISTANCE: f64 = 2.0;
+
+    /// Converts an inclination and a RAAN in degrees to a position in the
+    /// inner area, in cells.
+    fn to_cell(&self, inclination: f64, raan: f64) -> (f64, f64) {
+        let [min, max] = self.inclination_bounds;
+        (
+            raan / 360.0 * self.inner_area.width as f64,
+            (max - inclination) / (max - min) * self.inner_area.height as f64,
+        )
+    }
+
+    /// Returns the object nearest to the given position in the inner area,
+    /// if it is within [`Self::MAX_SELECTION_DISTANCE`] cells.
+    fn object_at<'a>(&self, objects: &'a [Object], position: Position) -> Option<&'a Object> {
+        let (x, y) = (position.x as f64 + 0.5, position.y as f64 + 0.5);
+        let distance = |object: &Object| {
+            let elements = object.elements();
+            let (cell_x, cell_y) = self.to_cell(elements.inclination, elements.right_ascension);
+            (cell_x - x).hypot(cell_y - y)
+        };
+        objects
+            .iter()
+            .map(|object| (object, distance(object)))
+            .filter(|&(_, distance)| distance <= Self::MAX_SELECTION_DISTANCE)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(object, _)| object)
+    }
+}
+
+impl Widget for Planes<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let elements: Vec<_> = self
+            .shared
+            .objects
+            .iter()
+            .map(|object| {
+                let elements = object.elements();
+                (elements.inclination, elements.right_ascension)
+            })
+            .collect();
+
+        // Fit the inclinations, since the objects of a constellation share a
+        // few inclinations
+        let (min, max) = elements
+            .iter()
+            .fold((f64::MAX, f64::MIN), |(min, max), &(inclination, _)| {
+                (min.min(inclination), max.max(inclination))
+            });
+        self.state.inclination_bounds = if elements.is_empty() {
+            [0.0, 180.0]
+        } else {
+            [
+                (min - PlanesState::INCLINATION_MARGIN).max(0.0),
+                (max + PlanesState::INCLINATION_MARGIN).min(180.0),
+            ]
+        };
+
+        let block = self.block();
+        self.state.inner_area = block.inner(area);
+        block.render(area, buf);
+
+        if elements.is_empty() {
+            Paragraph::new(t!("planes-no_objects").dark_gray())
+                .centered()
+                .wrap(Wrap { trim: true })
+                .render(self.state.inner_area, buf);
+            return;
+        }
+
+        let points: Vec<_> = elements
+            .iter()
+            .map(|&(inclination, raan)| (raan, inclination))
+            .collect();
+        let selected = self.shared.selected_object.as_ref().map(|object| {
+            let elements = object.elements();
+            (elements.right_ascension, elements.inclination)
+        });
+        Canvas::default()
+            .marker(self.shared.marker)
+            .x_bounds([0.0, 360.0])
+            .y_bounds(self.state.inclination_bounds)
+            .paint(|ctx| {
+                ctx.draw(&Points {
+                    coords: &points,
+                    color: Color::LightRed,
+                });
+                ctx.layer();
+                if let Some((raan, inclination)) = selected {
+                    ctx.print(raan, inclination, "+".light_green().bold());
+                }
+            })
+            .render(self.state.inner_area, buf);
+    }
+}
+
+impl Planes<'_> {
+    fn block(&self) -> Block<'static> {
+        let [min, max] = self.state.inclination_bounds;
+        let axes = t!(
+            "planes-axes",
+            min = format_number(min, Some(1)),
+            max = format_number(max, Some(1))
+        );
+        Block::new()
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_style(border_style(self.focused))
+            .title_bottom(Line::from(format!("({axes})")).dark_gray().right_aligned())
+    }
+}
+
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
+    if states.tab_state.selected != Tab::Planes {
+        return Ok(false);
+    }
+    if let Event::Mouse(event) = event {
+        handle_mouse_event(event, states);
+    }
+    Ok(false)
+}
+
+fn handle_mouse_event(event: MouseEvent, states: &mut States) {
+    let state = &states.planes_state;
+    let global_mouse = Position::new(event.column, event.row);
+    let Some(local_mouse) = window_to_area(global_mouse, state.inner_area) else {
+        return;
+    };
+    let MouseEventKind::Down(MouseButton::Left) = event.kind else {
+        return;
+    };
+
+    if let Some(object) = state
+        .object_at(&states.shared.objects, local_mouse)
+        .cloned()
+    {
+        states
+            .world_map_state
+            .follow_on_select(&object, &states.shared.time.time());
+        states.shared.selected_object = Some(object);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::test_object;
+
+    #[test]
+    fn click_selects_only_nearby_objects() {
+        let state = PlanesState {
+            inclination_bounds: [0.0, 180.0],
+            inner_area: Rect::new(0, 0, 360, 180),
+        };
+        let objects = [test_object(25544)];
+        // The test object has an inclination of 51.6° and a RAAN of 247.5°
+        let nearby = Position::new(248, 128);
+        let selected = state.object_at(&objects, nearby).unwrap();
+        assert_eq!(selected.elements().norad_id, 25544);
+        assert!(state.object_at(&objects, Position::new(200, 128)).is_none());
+        assert!(state.object_at(&[], nearby).is_none());
+    }
+}
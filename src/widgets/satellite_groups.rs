@@ -1,14 +1,26 @@
 use rayon::prelude::*;
 use rust_i18n::t;
-use std::time::{Duration, Instant};
+use std::{
+    collections::HashSet,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
 use tokio::{sync::mpsc, task::AbortHandle};
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::States, config::SatelliteGroupsConfig, event::Event, group::Group, object::Object,
-    widgets::window_to_area,
+    app::States,
+    config::{GroupOrder, ObjectOrder, SatelliteGroupsConfig},
+    event::Event,
+    group::Group,
+    log,
+    object::Object,
+    session::Session,
+    shared_state::SharedState,
+    widgets::{border_style, window_to_area},
 };
 use anyhow::Result;
-use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     prelude::*,
     style::Styled,
@@ -18,6 +30,8 @@ use ratatui::{
 /// A widget that displays a list of satellite groups.
 pub struct SatelliteGroups<'a> {
     pub state: &'a mut SatelliteGroupsState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
 }
 
 /// State of a [`SatelliteGroups`] widget.
@@ -38,37 +52,306 @@ pub struct SatelliteGroupsState {
     update_sender: mpsc::UnboundedSender<UpdateResult>,
     /// Receiver for async data updates.
     update_receiver: mpsc::UnboundedReceiver<UpdateResult>,
+    /// Label of the last entry that failed to load and has not been reported.
+    failed_label: Option<String>,
+    /// List file of the failed entry, if it could not be read.
+    unreadable_file: Option<PathBuf>,
+    /// Label of the last entry that loaded no objects and has not been
+    /// reported.
+    empty_label: Option<String>,
+    /// NORAD ID of the selected object to look up in the refreshed objects.
+    refreshed_selection: Option<u64>,
+    /// NORAD ID of the object to select once it is loaded.
+    pending_selection: Option<u64>,
+    /// Whether to deselect the selected object if it is missing after a
+    /// refresh.
+    deselect_missing_object: bool,
+    /// The order of the list entries.
+    group_order: GroupOrder,
+    /// The order of the loaded objects.
+    object_order: ObjectOrder,
+    /// Whether to display the number of objects of each group.
+    show_object_counts: bool,
+    /// Sender for the object counts of the cached groups.
+    count_sender: mpsc::UnboundedSender<(Group, usize)>,
+    /// Receiver for the object counts of the cached groups.
+    count_receiver: mpsc::UnboundedReceiver<(Group, usize)>,
 }
 
 impl SatelliteGroupsState {
     /// Creates a new `SatelliteGroupsState` with the given configuration.
-    pub fn with_config(config: SatelliteGroupsConfig) -> Self {
-        Self {
-            list_entries: config
-                .groups
-                .into_iter()
-                .map(Group::from)
-                .map(Entry::from)
-                .collect(),
+    ///
+    /// Entries of the favorite objects are listed first and start loading
+    /// immediately.
+    pub fn with_config(config: SatelliteGroupsConfig, favorites: &[u64]) -> Self {
+        let favorite_entries = favorites.iter().map(|&norad_id| Entry::favorite(norad_id));
+        let group_entries = config
+            .groups
+            .into_iter()
+            .enumerate()
+            .map(|(index, group)| Entry {
+                index,
+                selected: group.selected,
+                ..Entry::from(Group::from(group))
+            });
+        let mut state = Self {
+            list_entries: favorite_entries.chain(group_entries).collect(),
             cache_lifetime: Duration::from_mins(config.cache_lifetime_mins),
+            deselect_missing_object: config.deselect_missing_object,
+            group_order: config.group_order,
+            object_order: config.object_order,
+            show_object_counts: config.show_object_counts,
             ..Self::default()
+        };
+        state.sort_entries();
+
+        if config.show_object_counts && config.show_cached_object_counts {
+            state.read_cached_object_counts();
+        }
+
+        // Load the initially selected entries, ignoring caches older than the
+        // configured maximum startup cache age
+        let cache_lifetime = config
+            .max_startup_cache_age_mins
+            .map(Duration::from_mins)
+            .map_or(state.cache_lifetime, |age| age.min(state.cache_lifetime));
+        for index in 0..state.list_entries.len() {
+            if state.list_entries[index].selected {
+                state.load_entry_with_cache_lifetime(index, cache_lifetime);
+            }
+        }
+        state
+    }
+
+    /// Applies a new configuration, keeping the groups that are still
+    /// configured selected.
+    ///
+    /// All selected entries are reloaded, so the objects must be cleared.
+    pub fn reconfigure(&mut self, config: SatelliteGroupsConfig, favorites: &[u64]) {
+        self.cancel_all_loading();
+        let selected_groups: Vec<_> = self
+            .list_entries
+            .iter()
+            .filter(|entry| entry.selected && !entry.favorite)
+            .map(|entry| entry.group.clone())
+            .collect();
+
+        *self = Self::with_config(config, favorites);
+        for index in 0..self.list_entries.len() {
+            let entry = &mut self.list_entries[index];
+            if !entry.selected && selected_groups.contains(&entry.group) {
+                entry.selected = true;
+                self.load_entry(index);
+            }
         }
     }
 
+    /// Returns the labels of the selected groups, excluding the favorites.
+    pub fn selected_groups(&self) -> Vec<String> {
+        self.list_entries
+            .iter()
+            .filter(|entry| entry.selected && !entry.favorite)
+            .map(|entry| entry.group.label().to_owned())
+            .collect()
+    }
+
+    /// Selects the groups with the given labels and deselects the others,
+    /// keeping the favorites selected, then reloads the selected entries.
+    ///
+    /// Returns the labels that match no group. The objects must be cleared.
+    pub fn select_groups(&mut self, labels: &[String]) -> Vec<String> {
+        self.cancel_all_loading();
+        self.refreshed_selection = None;
+        self.pending_selection = None;
+        for entry in &mut self.list_entries {
+            if !entry.favorite {
+                entry.selected = labels.iter().any(|label| label == entry.group.label());
+            }
+        }
+        self.reload_selected_entries();
+
+        labels
+            .iter()
+            .filter(|label| {
+                !self
+                    .list_entries
+                    .iter()
+                    .any(|entry| !entry.favorite && entry.group.label() == label.as_str())
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Selects the object with the given NORAD ID once it is loaded.
+    pub fn select_when_loaded(&mut self, norad_id: u64) {
+        self.pending_selection = Some(norad_id);
+    }
+
+    /// Adds the object to the favorites if it is not one, otherwise removes it.
+    ///
+    /// Returns whether the object is a favorite afterwards. If it was removed,
+    /// the objects must be reloaded.
+    pub fn toggle_favorite(&mut self, norad_id: u64) -> bool {
+        let group = Group::from_norad_id(norad_id);
+        if let Some(index) = self
+            .list_entries
+            .iter()
+            .position(|entry| entry.favorite && entry.group == group)
+        {
+            self.cancel_entry_loading(index);
+            self.list_entries.remove(index);
+            return false;
+        }
+
+        let index = self
+            .list_entries
+            .iter()
+            .take_while(|entry| entry.favorite)
+            .count();
+        self.list_entries.insert(index, Entry::favorite(norad_id));
+        self.load_entry(index);
+        true
+    }
+
     /// Spawns async task to load orbital elements for a single entry.
     fn load_entry(&mut self, index: usize) {
+        self.load_entry_with_cache_lifetime(index, self.cache_lifetime);
+    }
+
+    /// Spawns async task to load orbital elements for a single entry, using
+    /// the cache only if it is younger than `cache_lifetime`.
+    fn load_entry_with_cache_lifetime(&mut self, index: usize, cache_lifetime: Duration) {
         let entry = &mut self.list_entries[index];
         entry.loading = true;
+        entry.empty = false;
         let tx = self.update_sender.clone();
         let group = entry.group.clone();
-        let cache_lifetime = self.cache_lifetime;
         let handle = tokio::spawn(async move {
             let elements = group.get_elements(cache_lifetime).await;
-            let _ = tx.send(UpdateResult { index, elements });
+            let _ = tx.send(UpdateResult { group, elements });
         });
         entry.abort_handle = Some(handle.abort_handle());
     }
 
+    /// Spawns async tasks to read the object counts of the unselected entries
+    /// from their caches.
+    fn read_cached_object_counts(&self) {
+        for entry in self.list_entries.iter().filter(|entry| !entry.selected) {
+            let tx = self.count_sender.clone();
+            let group = entry.group.clone();
+            tokio::spawn(async move {
+                if let Some(count) = group.cached_object_count().await {
+                    let _ = tx.send((group, count));
+                }
+            });
+        }
+    }
+
+    /// Sorts the list entries by the group order, keeping favorites first.
+    fn sort_entries(&mut self) {
+        match self.group_order {
+            GroupOrder::Config => self
+                .list_entries
+                .sort_by_key(|entry| (!entry.favorite, entry.index)),
+            GroupOrder::Name => self.list_entries.sort_by(|a, b| {
+                (!a.favorite, a.group.label()).cmp(&(!b.favorite, b.group.label()))
+            }),
+            GroupOrder::Selected => self
+                .list_entries
+                .sort_by_key(|entry| (!entry.favorite, !entry.selected)),
+        }
+    }
+
+    /// Sorts the objects by the object order.
+    ///
+    /// The objects are kept in fetch order if sorting by elevation without a
+    /// ground station.
+    fn sort_objects(&self, shared: &mut SharedState) {
+        let time = shared.time.time();
+        let key = |object: &Object| -> f64 {
+            let state = object.predict(&time).unwrap();
+            match (self.object_order, &shared.ground_station) {
+                (ObjectOrder::Altitude, _) => state.altitude(),
+                (ObjectOrder::Elevation, Some(station)) => {
+                    -state.position.az_el(&station.position).1
+                }
+                _ => 0.0,
+            }
+        };
+
+        match self.object_order {
+            ObjectOrder::Fetch => {}
+            ObjectOrder::Name => shared.objects.sort_by(|a, b| a.name().cmp(&b.name())),
+            ObjectOrder::Altitude | ObjectOrder::Elevation => {
+                let mut keyed: Vec<_> = shared
+                    .objects
+                    .drain(..)
+                    .map(|object| (key(&object), object))
+                    .collect();
+                keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+                shared.objects = keyed.into_iter().map(|(_, object)| object).collect();
+            }
+        }
+    }
+
+    /// Replaces the selected object with its refreshed counterpart from the
+    /// new objects, since the objects are rebuilt on refresh.
+    ///
+    /// Returns `true` if the selected object is no longer part of the
+    /// selected groups once the refresh completes, in which case it is
+    /// deselected if configured.
+    fn resolve_refreshed_selection(
+        &mut self,
+        selected: &mut Option<Object>,
+        new_objects: &[Object],
+    ) -> bool {
+        let Some(norad_id) = self.refreshed_selection else {
+            return false;
+        };
+        let selected_id = selected.as_ref().map(|object| object.elements().norad_id);
+        if selected_id != Some(norad_id) {
+            // The selection changed in the meantime
+            self.refreshed_selection = None;
+        } else if let Some(object) = new_objects
+            .iter()
+            .find(|object| object.elements().norad_id == norad_id)
+        {
+            *selected = Some(object.clone());
+            self.refreshed_selection = None;
+        } else if !self.is_loading() {
+            if self.deselect_missing_object {
+                *selected = None;
+            }
+            self.refreshed_selection = None;
+            return true;
+        }
+        false
+    }
+
+    /// Keeps the selected object across the reload of the objects after
+    /// deselecting the entry at the given index, resolving it again by NORAD
+    /// ID once loaded.
+    ///
+    /// An object loaded from the deselected entry is deselected, and only
+    /// selected again if another entry loads it.
+    fn keep_selection(&mut self, index: usize, selected: &mut Option<Object>) {
+        let Some(object) = selected else {
+            return;
+        };
+        let norad_id = object.elements().norad_id;
+        if object.group() == Some(self.list_entries[index].group.label()) {
+            *selected = None;
+            self.pending_selection = Some(norad_id);
+        } else {
+            self.refreshed_selection = Some(norad_id);
+        }
+    }
+
+    /// Returns whether any entry is loading.
+    fn is_loading(&self) -> bool {
+        self.list_entries.iter().any(|entry| entry.loading)
+    }
+
     /// Cancels the entry loading task at the given index.
     fn cancel_entry_loading(&mut self, index: usize) {
         let entry = &mut self.list_entries[index];
@@ -78,6 +361,13 @@ impl SatelliteGroupsState {
         entry.loading = false;
     }
 
+    /// Cancels the loading tasks of all entries.
+    pub fn cancel_all_loading(&mut self) {
+        for index in 0..self.list_entries.len() {
+            self.cancel_entry_loading(index);
+        }
+    }
+
     /// Spawns async tasks to reload orbital elements for all selected entries.
     /// Returns the loaded objects.
     pub fn reload_selected_entries(&mut self) -> Vec<Object> {
@@ -90,22 +380,84 @@ impl SatelliteGroupsState {
         objects
     }
 
+    /// Spawns async tasks to reload the selected entries, fetching the
+    /// entry at `index` regardless of the age of its cache.
+    ///
+    /// The objects of the entries are not told apart, so all selected entries
+    /// are reloaded and the objects must be cleared.
+    fn refresh_entry(&mut self, index: usize, selected_object: Option<&Object>) {
+        self.cancel_all_loading();
+        self.refreshed_selection = selected_object.map(|object| object.elements().norad_id);
+        for i in 0..self.list_entries.len() {
+            if i == index {
+                self.load_entry_with_cache_lifetime(i, Duration::ZERO);
+            } else if self.list_entries[i].selected {
+                self.load_entry(i);
+            }
+        }
+    }
+
     /// Polls for async entry update results and returns new objects.
     pub fn poll_entry_updates(&mut self) -> Vec<Object> {
+        // Counts of loaded entries are more recent than the cached ones
+        while let Ok((group, count)) = self.count_receiver.try_recv() {
+            if let Some(entry) = self
+                .list_entries
+                .iter_mut()
+                .find(|entry| entry.group == group && entry.object_count.is_none())
+            {
+                entry.object_count = Some(count);
+            }
+        }
+
         let mut new_objects = Vec::new();
         while let Ok(result) = self.update_receiver.try_recv() {
-            let entry = &mut self.list_entries[result.index];
+            // Entries may have been added or removed since the task started
+            let Some(entry) = self
+                .list_entries
+                .iter_mut()
+                .find(|entry| entry.group == result.group)
+            else {
+                continue;
+            };
             entry.loading = false;
             entry.abort_handle = None;
             if let Some(elements) = result.elements {
+                entry.object_count = Some(elements.len());
+                // A valid but empty group is not a failure, but worth noting
+                if elements.is_empty() {
+                    log::push(format!("{} has no objects", entry.group.label()));
+                    entry.empty = true;
+                    self.empty_label = Some(entry.group.label().to_owned());
+                    continue;
+                }
+                // Label favorites with the object name once known
+                if entry.favorite
+                    && let Some(name) = elements.first().and_then(|e| e.object_name.as_ref())
+                {
+                    entry.group.set_label(name.clone());
+                }
+                log::push(format!(
+                    "Loaded {} objects from {}",
+                    elements.len(),
+                    entry.group.label()
+                ));
+                let label = entry.group.label();
                 new_objects.extend(
                     elements
                         .into_par_iter()
-                        .map(Object::from_elements)
+                        .map(|elements| Object::from_elements(elements).with_group(label))
                         .collect::<Vec<_>>(),
                 );
             } else {
+                log::push(format!("Failed to load {}", entry.group.label()));
                 entry.selected = false;
+                self.failed_label = Some(entry.group.label().to_owned());
+                self.unreadable_file = entry
+                    .group
+                    .list_file()
+                    .filter(|path| !path.is_file())
+                    .map(ToOwned::to_owned);
             }
         }
         new_objects
@@ -129,21 +481,33 @@ impl SatelliteGroupsState {
 impl Default for SatelliteGroupsState {
     fn default() -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
+        let (count_sender, count_receiver) = mpsc::unbounded_channel();
         Self {
             list_entries: Default::default(),
             list_state: Default::default(),
             inner_area: Default::default(),
             cache_lifetime: Default::default(),
             last_update_instant: Instant::now(),
+            failed_label: None,
+            unreadable_file: None,
+            empty_label: None,
+            refreshed_selection: None,
+            pending_selection: None,
+            deselect_missing_object: true,
+            group_order: GroupOrder::default(),
+            object_order: ObjectOrder::default(),
+            show_object_counts: false,
             update_sender: tx,
             update_receiver: rx,
+            count_sender,
+            count_receiver,
         }
     }
 }
 
 impl Widget for SatelliteGroups<'_> {
     fn render(mut self, area: Rect, buf: &mut Buffer) {
-        let block = Self::block();
+        let block = self.block();
         self.state.inner_area = block.inner(area);
         block.render(area, buf);
 
@@ -153,14 +517,19 @@ impl Widget for SatelliteGroups<'_> {
 }
 
 impl SatelliteGroups<'_> {
-    fn block() -> Block<'static> {
-        Block::bordered().title(t!("group-title").to_string().blue())
+    fn block(&self) -> Block<'static> {
+        Block::bordered()
+            .border_style(border_style(self.focused))
+            .title(t!("group-title").to_string().blue())
     }
 
     fn list(&self) -> List<'static> {
+        let width = self.state.inner_area.width as usize;
         let items = self.state.list_entries.iter().map(|entry| {
             let icon = if entry.loading {
                 "⋯"
+            } else if entry.selected && entry.empty {
+                "∅"
             } else if entry.selected {
                 "✓"
             } else {
@@ -171,7 +540,20 @@ impl SatelliteGroups<'_> {
             } else {
                 Style::new()
             };
-            ListItem::new(format!("{} {}", icon, entry.group.label()).set_style(style))
+            let marker = if entry.favorite { "★ " } else { "" };
+            let label = format!("{} {marker}{}", icon, entry.group.label());
+            // Right-align the dimmed object count
+            let count = entry
+                .object_count
+                .filter(|_| self.state.show_object_counts)
+                .map(|count| format!(" {count}"))
+                .unwrap_or_default();
+            let padding = width.saturating_sub(label.width() + count.width());
+            ListItem::new(Line::from(vec![
+                label.set_style(style),
+                " ".repeat(padding).into(),
+                count.dark_gray(),
+            ]))
         });
         List::new(items).highlight_style(Style::new().add_modifier(Modifier::REVERSED))
     }
@@ -198,8 +580,8 @@ impl SatelliteGroups<'_> {
 
 /// Result of an async satellite group update task.
 struct UpdateResult {
-    /// Index of the entry in the list that was updated.
-    index: usize,
+    /// The satellite group that was updated.
+    group: Group,
     /// Fetched orbital elements, or `None` if the fetch failed.
     elements: Option<Vec<sgp4::Elements>>,
 }
@@ -212,6 +594,14 @@ pub struct Entry {
     selected: bool,
     /// Whether this entry is currently loading data.
     loading: bool,
+    /// Whether the last load of this entry returned no objects.
+    empty: bool,
+    /// The number of objects of the last load, or of the cache.
+    object_count: Option<usize>,
+    /// Whether this entry is a favorite object rather than a configured group.
+    favorite: bool,
+    /// The position of the group in the configuration.
+    index: usize,
     /// Handle to abort the loading task.
     abort_handle: Option<AbortHandle>,
 }
@@ -222,32 +612,212 @@ impl From<Group> for Entry {
             group,
             selected: false,
             loading: false,
+            empty: false,
+            object_count: None,
+            favorite: false,
+            index: 0,
             abort_handle: None,
         }
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+impl Entry {
+    /// Creates a selected entry for the favorite object with the given NORAD
+    /// ID.
+    fn favorite(norad_id: u64) -> Self {
+        Self {
+            selected: true,
+            favorite: true,
+            ..Self::from(Group::from_norad_id(norad_id))
+        }
+    }
+}
+
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
         Event::Update => {
             handle_update_event(states);
-            Ok(())
+            Ok(false)
         }
-        Event::Mouse(event) => handle_mouse_event(event, states),
-        _ => Ok(()),
+        Event::Key(event) => handle_key_event(event, states),
+        Event::Mouse(event) => handle_mouse_event(event, states).map(|()| false),
+        _ => Ok(false),
     }
 }
 
+fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<bool> {
+    match event.code {
+        // Toggle whether the selected object is a favorite.
+        KeyCode::Char('F') => {
+            let Some(selected) = &states.shared.selected_object else {
+                return Ok(true);
+            };
+            let norad_id = selected.elements().norad_id;
+            let name = selected.name().unwrap_or("UNK").to_owned();
+
+            let state = &mut states.satellite_groups_state;
+            let favorites = &mut states.shared.favorites;
+            if state.toggle_favorite(norad_id) {
+                favorites.push(norad_id);
+                states
+                    .toast_state
+                    .show(t!("toast-favorite_added", name = name));
+            } else {
+                favorites.retain(|&id| id != norad_id);
+                states.shared.objects.clear();
+                state.reload_selected_entries();
+                states
+                    .toast_state
+                    .show(t!("toast-favorite_removed", name = name));
+            }
+
+            let favorites = favorites.clone();
+            if let Err(e) = Session::update(|session| session.favorites = favorites) {
+                log::push(format!("Failed to save session: {e}"));
+            }
+        }
+        // Fetch the hovered group regardless of its cache.
+        KeyCode::Char('u') => {
+            let state = &mut states.satellite_groups_state;
+            let Some(index) = state
+                .list_state
+                .selected()
+                .filter(|&index| state.list_entries[index].selected)
+            else {
+                return Ok(true);
+            };
+            let label = state.list_entries[index].group.label().to_owned();
+            state.refresh_entry(index, states.shared.selected_object.as_ref());
+            states.shared.objects.clear();
+            states
+                .toast_state
+                .show(t!("toast-group_refreshing", label = label));
+        }
+        // Cycle the order of the groups.
+        KeyCode::Char('s') => {
+            let state = &mut states.satellite_groups_state;
+            state.group_order = state.group_order.next();
+            state.sort_entries();
+        }
+        // Cycle the order of the objects.
+        KeyCode::Char('S') => {
+            let state = &mut states.satellite_groups_state;
+            state.object_order = state.object_order.next();
+            if state.object_order == ObjectOrder::Fetch {
+                // Reload the objects to restore the fetch order
+                states.shared.objects.clear();
+                state.reload_selected_entries();
+            } else {
+                state.sort_objects(&mut states.shared);
+            }
+        }
+        // Select the next favorite object.
+        KeyCode::Char('g') => {
+            let shared = &mut states.shared;
+            let current = shared.selected_object.as_ref().and_then(|selected| {
+                let norad_id = selected.elements().norad_id;
+                shared.favorites.iter().position(|&id| id == norad_id)
+            });
+            let start = current.map_or(0, |index| index + 1);
+            let len = shared.favorites.len();
+            let next = (0..len)
+                .map(|offset| shared.favorites[(start + offset) % len])
+                .find_map(|norad_id| {
+                    shared
+                        .objects
+                        .iter()
+                        .find(|object| object.elements().norad_id == norad_id)
+                });
+            if let Some(object) = next {
+                states
+                    .world_map_state
+                    .follow_on_select(object, &shared.time.time());
+                shared.selected_object = Some(object.clone());
+            }
+        }
+        _ => return Ok(false),
+    }
+
+    Ok(true)
+}
+
+/// Appends the new objects that are not loaded yet, e.g. a favorite that is
+/// also part of a selected group.
+fn extend_unique(objects: &mut Vec<Object>, new_objects: Vec<Object>) {
+    let mut loaded: HashSet<u64> = objects
+        .iter()
+        .map(|object| object.elements().norad_id)
+        .collect();
+    objects.extend(
+        new_objects
+            .into_iter()
+            .filter(|object| loaded.insert(object.elements().norad_id)),
+    );
+}
+
 /// Handle update events.
 fn handle_update_event(states: &mut States) {
     let state = &mut states.satellite_groups_state;
 
     // Poll for async update results
     let new_objects = state.poll_entry_updates();
-    states.shared.objects.extend(new_objects);
+
+    // Select the object requested before its group was loaded
+    if let Some(norad_id) = state.pending_selection {
+        if let Some(object) = new_objects
+            .iter()
+            .find(|object| object.elements().norad_id == norad_id)
+        {
+            states.shared.selected_object = Some(object.clone());
+            state.pending_selection = None;
+        } else if !state.is_loading() {
+            state.pending_selection = None;
+        }
+    }
+
+    let selected_name = states
+        .shared
+        .selected_object
+        .as_ref()
+        .map(|object| object.name().unwrap_or("UNK").to_owned());
+    if state.resolve_refreshed_selection(&mut states.shared.selected_object, &new_objects)
+        && let Some(name) = selected_name
+    {
+        let message = if state.deselect_missing_object {
+            t!("toast-selection_lost", name = name)
+        } else {
+            t!("toast-selection_stale", name = name)
+        };
+        states.toast_state.show(message);
+    }
+    if !new_objects.is_empty() {
+        extend_unique(&mut states.shared.objects, new_objects);
+        state.sort_objects(&mut states.shared);
+    }
+    if let Some(label) = state.failed_label.take() {
+        let message = match state.unreadable_file.take() {
+            Some(path) => t!(
+                "toast-list_file_unreadable",
+                label = label,
+                path = path.display()
+            ),
+            None => t!("toast-load_failed", label = label),
+        };
+        states.toast_state.show(message);
+    }
+    if let Some(label) = state.empty_label.take() {
+        states
+            .toast_state
+            .show(t!("toast-group_empty", label = label));
+    }
 
     let now = Instant::now();
     if now.duration_since(state.last_update_instant) >= state.cache_lifetime {
+        state.refreshed_selection = states
+            .shared
+            .selected_object
+            .as_ref()
+            .map(|object| object.elements().norad_id);
         states.shared.objects.clear();
         state.reload_selected_entries();
         state.last_update_instant = now;
@@ -269,11 +839,11 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
             if let Some(index) = state.list_state.selected() {
                 let was_selected = state.list_entries[index].selected;
                 state.list_entries[index].selected = !was_selected;
-                states.shared.selected_object = None;
 
                 if was_selected {
                     // Deselecting: cancel if loading
                     state.cancel_entry_loading(index);
+                    state.keep_selection(index, &mut states.shared.selected_object);
                     states.shared.objects.clear();
                     state.reload_selected_entries();
                 } else {
@@ -298,3 +868,207 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{group::test_group, object::test_object as object};
+
+    #[test]
+    fn missing_selection_is_reported_after_refresh() {
+        let mut state = SatelliteGroupsState {
+            refreshed_selection: Some(25544),
+            ..Default::default()
+        };
+        let mut selected = Some(object(25544));
+        assert!(state.resolve_refreshed_selection(&mut selected, &[object(20580)]));
+        assert!(selected.is_none());
+        assert!(state.refreshed_selection.is_none());
+
+        // Reported only once
+        assert!(!state.resolve_refreshed_selection(&mut selected, &[]));
+    }
+
+    #[test]
+    fn missing_selection_is_kept_if_configured() {
+        let mut state = SatelliteGroupsState {
+            refreshed_selection: Some(25544),
+            deselect_missing_object: false,
+            ..Default::default()
+        };
+        let mut selected = Some(object(25544));
+        assert!(state.resolve_refreshed_selection(&mut selected, &[]));
+        assert!(selected.is_some());
+    }
+
+    #[test]
+    fn selection_is_kept_when_toggling_another_group() {
+        let entry = |norad_id, label| {
+            let mut group = Group::from_norad_id(norad_id);
+            group.set_label(label);
+            Entry {
+                selected: true,
+                ..Entry::from(group)
+            }
+        };
+        let mut state = SatelliteGroupsState {
+            list_entries: vec![entry(25544, "Stations"), entry(20580, "Science")],
+            ..Default::default()
+        };
+
+        // Deselecting the second group keeps the object of the first one
+        let mut selected = Some(object(25544).with_group("Stations"));
+        state.keep_selection(1, &mut selected);
+        assert!(selected.is_some());
+        assert_eq!(state.refreshed_selection, Some(25544));
+        let reloaded = object(25544).with_group("Stations");
+        assert!(!state.resolve_refreshed_selection(&mut selected, &[reloaded]));
+        assert_eq!(
+            selected.map(|object| object.elements().norad_id),
+            Some(25544)
+        );
+
+        // Deselecting the group of the object deselects it
+        let mut selected = Some(object(25544).with_group("Stations"));
+        state.keep_selection(0, &mut selected);
+        assert!(selected.is_none());
+        assert_eq!(state.pending_selection, Some(25544));
+    }
+
+    #[test]
+    fn refreshed_selection_is_replaced() {
+        let mut state = SatelliteGroupsState {
+            refreshed_selection: Some(25544),
+            ..Default::default()
+        };
+        let mut selected = Some(object(25544));
+        let refreshed = object(25544);
+        assert!(!state.resolve_refreshed_selection(&mut selected, &[refreshed]));
+        assert!(selected.is_some());
+        assert!(state.refreshed_selection.is_none());
+    }
+
+    #[test]
+    fn refreshed_selection_is_found_among_reordered_objects() {
+        let mut state = SatelliteGroupsState {
+            refreshed_selection: Some(25544),
+            ..Default::default()
+        };
+        let mut selected = Some(object(25544));
+
+        // The refreshed elements are a day newer and listed in another order
+        let mut elements = object(25544).elements().clone();
+        elements.datetime += chrono::Duration::days(1);
+        let refreshed = Object::from_elements(elements);
+        let new_objects = [object(20580), refreshed.clone(), object(43013)];
+        assert!(!state.resolve_refreshed_selection(&mut selected, &new_objects));
+
+        let selected = selected.unwrap();
+        assert_eq!(selected.elements().norad_id, 25544);
+        assert_eq!(selected.epoch(), refreshed.epoch());
+        assert_ne!(selected.epoch(), object(25544).epoch());
+    }
+
+    #[test]
+    fn loaded_objects_are_not_duplicated() {
+        let mut objects = vec![object(25544)];
+        extend_unique(
+            &mut objects,
+            vec![object(20580), object(25544), object(20580)],
+        );
+        let ids: Vec<_> = objects
+            .iter()
+            .map(|object| object.elements().norad_id)
+            .collect();
+        assert_eq!(ids, [25544, 20580]);
+    }
+
+    #[test]
+    fn empty_group_is_reported() {
+        let group = Group::from_norad_id(25544);
+        let mut state = SatelliteGroupsState {
+            list_entries: vec![Entry {
+                selected: true,
+                loading: true,
+                ..Entry::from(group.clone())
+            }],
+            ..Default::default()
+        };
+        state
+            .update_sender
+            .send(UpdateResult {
+                group,
+                elements: Some(Vec::new()),
+            })
+            .unwrap();
+
+        assert!(state.poll_entry_updates().is_empty());
+        let entry = &state.list_entries[0];
+        assert!(entry.empty && entry.selected && !entry.loading);
+        assert_eq!(entry.object_count, Some(0));
+        assert_eq!(state.empty_label.as_deref(), Some("25544"));
+        assert!(state.failed_label.is_none());
+    }
+
+    #[test]
+    fn unreadable_list_file_is_reported() {
+        let group = test_group("unreadable_list_file");
+        let mut state = SatelliteGroupsState {
+            list_entries: vec![Entry {
+                selected: true,
+                loading: true,
+                ..Entry::from(group.clone())
+            }],
+            ..Default::default()
+        };
+        state
+            .update_sender
+            .send(UpdateResult {
+                group: group.clone(),
+                elements: None,
+            })
+            .unwrap();
+
+        assert!(state.poll_entry_updates().is_empty());
+        assert!(!state.list_entries[0].selected);
+        assert_eq!(state.failed_label.as_deref(), Some("unreadable_list_file"));
+        assert_eq!(state.unreadable_file.as_deref(), group.list_file());
+    }
+
+    #[tokio::test]
+    async fn cancelled_load_keeps_cache() {
+        // A load removes the corrupted cache before fetching again
+        let group = test_group("cancelled-load");
+        let cache_path = group.cache_path();
+        let json = "[{\"OBJECT_NAME\":";
+        std::fs::write(&cache_path, json).unwrap();
+
+        let mut state = SatelliteGroupsState {
+            list_entries: vec![Entry {
+                selected: true,
+                ..Entry::from(group)
+            }],
+            ..Default::default()
+        };
+        state.load_entry_with_cache_lifetime(0, Duration::MAX);
+        state.cancel_all_loading();
+        tokio::task::yield_now().await;
+
+        assert!(!state.is_loading());
+        assert!(state.update_receiver.try_recv().is_err());
+        assert_eq!(std::fs::read_to_string(&cache_path).unwrap(), json);
+
+        // Without cancelling, the same load removes the cache
+        state.load_entry_with_cache_lifetime(0, Duration::MAX);
+        assert!(
+            state
+                .update_receiver
+                .recv()
+                .await
+                .unwrap()
+                .elements
+                .is_none()
+        );
+        assert!(!cache_path.exists());
+    }
+}
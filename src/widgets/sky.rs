@@ -1,5 +1,7 @@
+use std::iter;
+
 use anyhow::Result;
-use crossterm::event::MouseEvent;
+use crossterm::event::{KeyCode, KeyEvent, MouseEvent};
 use ratatui::{
     prelude::*,
     widgets::{
@@ -8,16 +10,26 @@ use ratatui::{
     },
 };
 use rust_i18n::t;
+use tokio::sync::mpsc;
 
 use crate::{
-    app::States, config::SkyConfig, coordinates::Lla, event::Event, shared_state::SharedState,
-    utils::*, widgets::window_to_area,
+    app::States,
+    config::SkyConfig,
+    coordinates::Lla,
+    event::Event,
+    format::format_number,
+    gpsd,
+    shared_state::{SharedState, Station},
+    utils::*,
+    widgets::{border_style, window_to_area},
 };
 
 /// A widget that displays the sky track on a polar plot.
 pub struct Sky<'a> {
     pub state: &'a mut SkyState,
     pub shared: &'a SharedState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
 }
 
 /// State of a [`Sky`] widget.
@@ -25,29 +37,87 @@ pub struct Sky<'a> {
 pub struct SkyState {
     /// The area of the canvas.
     canvas_area: Rect,
+    /// The area of the elevation plot, empty if it is hidden.
+    plot_area: Rect,
+    /// Whether to display the elevation plot.
+    show_elevation_plot: bool,
+    /// Colors of the sky track for the low, medium and high elevations.
+    track_colors: [Color; 3],
+    /// Elevations in degrees from which the medium and high colors are used.
+    track_elevation_thresholds_deg: [f64; 2],
+    /// Azimuth in degrees displayed at the top of the polar plot.
+    rotation_deg: f64,
     /// Current mouse position within the canvas's area.
     mouse_position: Option<(f64, f64)>,
     /// The inner rendering area of the widget.
     inner_area: Rect,
+    /// The configured name of the ground station.
+    station_name: Option<String>,
+    /// Receiver for ground station positions from gpsd.
+    gpsd_receiver: Option<mpsc::UnboundedReceiver<Lla>>,
 }
 
 impl SkyState {
-    pub fn with_config(_config: SkyConfig) -> Self {
-        Self::default()
+    /// Creates a new `SkyState` with the given configuration.
+    ///
+    /// If `use_gpsd` is set, spawns an async task polling the ground station
+    /// position from gpsd.
+    pub fn with_config(config: SkyConfig) -> Self {
+        Self {
+            station_name: config.ground_station.and_then(|station| station.name),
+            show_elevation_plot: config.show_elevation_plot,
+            track_colors: config.track_palette.colors(),
+            track_elevation_thresholds_deg: config.track_elevation_thresholds_deg,
+            rotation_deg: config.rotation_deg.rem_euclid(360.0),
+            gpsd_receiver: config
+                .use_gpsd
+                .then(|| gpsd::spawn_poller(config.gpsd_address)),
+            ..Self::default()
+        }
+    }
+
+    /// Applies a new configuration, keeping the rotation of the polar plot.
+    pub fn reconfigure(&mut self, config: SkyConfig) {
+        let rotation_deg = self.rotation_deg;
+        *self = Self::with_config(config);
+        self.rotation_deg = rotation_deg;
+    }
+
+    /// Returns the color of the sky track at the given elevation in degrees.
+    fn track_color(&self, elevation: f64) -> Color {
+        let [medium, high] = self.track_elevation_thresholds_deg;
+        let [low_color, medium_color, high_color] = self.track_colors;
+        if elevation >= high {
+            high_color
+        } else if elevation >= medium {
+            medium_color
+        } else {
+            low_color
+        }
+    }
+
+    /// Step in degrees by which the polar plot is rotated.
+    const ROTATION_STEP_DEG: f64 = 45.0;
+
+    /// Converts azimuth and elevation to canvas coordinates, taking the
+    /// rotation of the polar plot into account.
+    fn to_canvas(&self, az: f64, el: f64) -> (f64, f64) {
+        az_el_to_canvas(az - self.rotation_deg, el)
     }
 
     fn hovered_az_el(&self) -> Option<(f64, f64)> {
         let (x, y) = self.mouse_position?;
-        Some(canvas_to_az_el(x, y))
+        let (az, el) = canvas_to_az_el(x, y);
+        Some(((az + self.rotation_deg).rem_euclid(360.0), el))
     }
 }
 
 impl Widget for Sky<'_> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
+    fn render(mut self, area: Rect, buf: &mut Buffer) {
         let block = self.block();
         self.state.inner_area = block.inner(area);
         block.render(area, buf);
-        self.state.canvas_area = centered_square(self.state.inner_area);
+        self.layout();
 
         if self
             .state
@@ -74,12 +144,34 @@ impl Widget for Sky<'_> {
         }
 
         self.render_graph(buf);
+        self.render_elevation_plot(buf);
     }
 }
 
 impl Sky<'_> {
+    /// The height of the elevation plot.
+    const PLOT_HEIGHT: u16 = 5;
+
+    /// Splits the inner area between the sky chart and the elevation plot,
+    /// hiding the plot if there is not enough space.
+    fn layout(&mut self) {
+        let inner_area = self.state.inner_area;
+        if self.state.show_elevation_plot && inner_area.height >= Self::PLOT_HEIGHT * 3 {
+            let [chart_area, plot_area] =
+                Layout::vertical([Constraint::Fill(1), Constraint::Length(Self::PLOT_HEIGHT)])
+                    .areas(inner_area);
+            self.state.canvas_area = centered_square(chart_area);
+            self.state.plot_area = plot_area;
+        } else {
+            self.state.canvas_area = centered_square(inner_area);
+            self.state.plot_area = Rect::default();
+        }
+    }
+
     fn block(&self) -> Block<'static> {
-        let mut block = Block::new().borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM);
+        let mut block = Block::new()
+            .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_style(border_style(self.focused));
         if let Some((az, el)) = self.state.hovered_az_el() {
             block =
                 block.title_bottom(Line::from(format!("Az {az:.1}°, El {el:.1}°")).right_aligned());
@@ -89,26 +181,81 @@ impl Sky<'_> {
 
     fn render_graph(&self, buf: &mut Buffer) {
         Canvas::default()
+            .marker(self.shared.marker)
             .x_bounds([-1.0, 1.0])
             .y_bounds([-1.0, 1.0])
             .paint(|ctx| {
-                Self::draw_grid(ctx);
+                self.draw_grid(ctx);
                 ctx.layer();
                 self.draw_sky_track(ctx, &self.shared.ground_station.as_ref().unwrap().position);
             })
             .render(self.state.canvas_area, buf);
     }
 
-    fn draw_grid(ctx: &mut Context) {
+    /// Renders the elevation of the selected object over the window of the
+    /// sky track, labeling the peak.
+    fn render_elevation_plot(&self, buf: &mut Buffer) {
+        if self.state.plot_area.is_empty() {
+            return;
+        }
+        let (Some(station), Some(object)) =
+            (&self.shared.ground_station, &self.shared.selected_object)
+        else {
+            return;
+        };
+        let window = SKY_TRACK_WINDOW_MINUTES as f64;
+        let profile =
+            calculate_elevation_profile(object, &station.position, &self.shared.time.time());
+
+        Canvas::default()
+            .marker(self.shared.marker)
+            .x_bounds([-window, window])
+            .y_bounds([0.0, 90.0])
+            .paint(|ctx| {
+                ctx.draw(&canvas::Line::new(
+                    -window,
+                    0.0,
+                    window,
+                    0.0,
+                    Color::DarkGray,
+                ));
+                ctx.draw(&canvas::Line::new(0.0, 0.0, 0.0, 90.0, Color::LightRed));
+                ctx.layer();
+                // Only draw the segments above the horizon
+                for pair in profile.windows(2) {
+                    let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+                    if y1 >= 0.0 && y2 >= 0.0 {
+                        ctx.draw(&canvas::Line {
+                            x1,
+                            y1,
+                            x2,
+                            y2,
+                            color: Color::LightBlue,
+                        });
+                    }
+                }
+                if let Some(&(x, el)) = profile.iter().max_by(|a, b| a.1.total_cmp(&b.1))
+                    && el >= 0.0
+                {
+                    ctx.print(x, el, format!("{}°", format_number(el, Some(1))).white());
+                }
+            })
+            .render(self.state.plot_area, buf);
+    }
+
+    fn draw_grid(&self, ctx: &mut Context) {
         for radius in [1.0, 0.67, 0.33] {
             ctx.draw(&Circle::new(0.0, 0.0, radius, Color::DarkGray));
         }
-        ctx.draw(&canvas::Line::new(-1.0, 0.0, 1.0, 0.0, Color::DarkGray));
-        ctx.draw(&canvas::Line::new(0.0, -1.0, 0.0, 1.0, Color::DarkGray));
-        ctx.print(0.0, 1.0, "N".green());
-        ctx.print(1.0, 0.0, "E".green());
-        ctx.print(0.0, -1.0, "S".green());
-        ctx.print(-1.0, 0.0, "W".green());
+        for az in [0.0, 90.0] {
+            let (x1, y1) = self.state.to_canvas(az, 0.0);
+            let (x2, y2) = self.state.to_canvas(az + 180.0, 0.0);
+            ctx.draw(&canvas::Line::new(x1, y1, x2, y2, Color::DarkGray));
+        }
+        for (label, az) in [("N", 0.0), ("E", 90.0), ("S", 180.0), ("W", 270.0)] {
+            let (x, y) = self.state.to_canvas(az, 0.0);
+            ctx.print(x, y, label.green());
+        }
     }
 
     /// Draw the sky track on the polar plot.
@@ -120,38 +267,36 @@ impl Sky<'_> {
         };
         let time = self.shared.time.time();
 
-        let points = calculate_sky_track(object, station_position, &time);
-        Self::draw_lines(ctx, points, Color::LightBlue);
+        // Color each segment by the higher elevation of its ends
+        let points = calculate_sky_track(object, station_position, &time, self.state.rotation_deg);
+        for window in points.windows(2) {
+            let (x1, y1) = window[0];
+            let (x2, y2) = window[1];
+            let elevation = canvas_to_az_el(x1, y1).1.max(canvas_to_az_el(x2, y2).1);
+            ctx.draw(&canvas::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                color: self.state.track_color(elevation),
+            });
+        }
 
         // Draw current satellite position if visible
         let object_state = object.predict(&time).unwrap();
         let (az, el) = object_state.position.az_el(station_position);
         if el >= 0.0 {
-            let (x, y) = az_el_to_canvas(az, el);
+            let (x, y) = self.state.to_canvas(az, el);
             let object_name = object.name().unwrap_or(UNKNOWN_NAME);
             ctx.print(
                 x,
                 y,
-                "+".light_red().slow_blink() + format!(" {object_name}").white(),
+                "+".light_red().add_modifier(self.shared.emphasis())
+                    + format!(" {object_name}").white(),
             );
         }
     }
 
-    /// Draws lines between points.
-    fn draw_lines(ctx: &mut Context, points: Vec<(f64, f64)>, color: Color) {
-        for window in points.windows(2) {
-            let (x1, y1) = window[0];
-            let (x2, y2) = window[1];
-            ctx.draw(&canvas::Line {
-                x1,
-                y1,
-                x2,
-                y2,
-                color,
-            });
-        }
-    }
-
     fn centered_paragraph<'a>(text: impl Into<Text<'a>>) -> Paragraph<'a> {
         Paragraph::new(text).centered().wrap(Wrap { trim: true })
     }
@@ -168,10 +313,49 @@ fn centered_square(area: Rect) -> Rect {
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
-        Event::Mouse(event) => handle_mouse_event(event, states),
-        _ => Ok(()),
+        Event::Update => {
+            handle_update_event(states);
+            Ok(false)
+        }
+        Event::Key(event) => Ok(handle_key_event(event, states)),
+        Event::Mouse(event) => handle_mouse_event(event, states).map(|()| false),
+        _ => Ok(false),
+    }
+}
+
+fn handle_key_event(event: KeyEvent, states: &mut States) -> bool {
+    let state = &mut states.sky_state;
+    match event.code {
+        // Toggle the elevation plot.
+        KeyCode::Char('P') => state.show_elevation_plot = !state.show_elevation_plot,
+        // Rotate the polar plot clockwise, so the next azimuth is up.
+        KeyCode::Char('U') => {
+            state.rotation_deg =
+                (state.rotation_deg + SkyState::ROTATION_STEP_DEG).rem_euclid(360.0);
+            states.toast_state.show(t!(
+                "toast-sky_rotation",
+                azimuth = format_number(state.rotation_deg, Some(0))
+            ));
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Handle update events.
+fn handle_update_event(states: &mut States) {
+    let Some(receiver) = &mut states.sky_state.gpsd_receiver else {
+        return;
+    };
+
+    // Move the ground station to the latest position reported by gpsd
+    if let Some(position) = iter::from_fn(|| receiver.try_recv().ok()).last() {
+        states.shared.ground_station = Some(Station::new(
+            states.sky_state.station_name.clone(),
+            position,
+        ));
     }
 }
 
@@ -196,3 +380,34 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TrackPalette;
+
+    #[test]
+    fn track_color_follows_thresholds() {
+        let state = SkyState::with_config(SkyConfig {
+            track_palette: TrackPalette::TrafficLight,
+            ..SkyConfig::default()
+        });
+        assert_eq!(state.track_color(5.0), Color::LightRed);
+        assert_eq!(state.track_color(10.0), Color::LightYellow);
+        assert_eq!(state.track_color(60.0), Color::LightGreen);
+    }
+
+    #[test]
+    fn rotation_puts_azimuth_up() {
+        let mut state = SkyState::with_config(SkyConfig {
+            rotation_deg: 90.0,
+            ..SkyConfig::default()
+        });
+        let (x, y) = state.to_canvas(90.0, 0.0);
+        assert!(x.abs() < 1e-9 && (y - 1.0).abs() < 1e-9);
+
+        state.mouse_position = Some(state.to_canvas(300.0, 45.0));
+        let (az, el) = state.hovered_az_el().unwrap();
+        assert!((az - 300.0).abs() < 1e-9 && (el - 45.0).abs() < 1e-9);
+    }
+}
@@ -4,43 +4,34 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::{prelude::*, widgets::Block};
 use rust_i18n::t;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     app::States,
+    config::PlanesConfig,
     event::Event,
     shared_state::SharedState,
     widgets::{
+        border_style,
         information::{Information, InformationState},
+        planes::{Planes, PlanesState},
         sky::{Sky, SkyState},
     },
 };
 
 /// Tabs enum for the right-side panel.
-#[derive(Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Tab {
     #[default]
     Info,
     Sky,
+    Planes,
 }
 
 impl Tab {
-    /// Returns an iterator over all tabs.
-    pub fn iter() -> impl Iterator<Item = Self> {
-        [Self::Info, Self::Sky].into_iter()
-    }
-
-    /// Returns the next tab.
-    fn next(&self) -> Self {
-        match self {
-            Tab::Sky => Tab::Info,
-            Tab::Info => Tab::Sky,
-        }
-    }
-
-    /// Returns the previous tab.
-    fn previous(&self) -> Self {
-        self.next()
-    }
+    /// All tabs, in display order.
+    const ALL: [Self; 3] = [Self::Info, Self::Sky, Self::Planes];
 }
 
 impl Display for Tab {
@@ -48,6 +39,7 @@ impl Display for Tab {
         match self {
             Tab::Info => write!(f, "{}", t!("info-title")),
             Tab::Sky => write!(f, "{}", t!("sky-title")),
+            Tab::Planes => write!(f, "{}", t!("planes-title")),
         }
     }
 }
@@ -57,11 +49,61 @@ pub struct Tabs<'a> {
     pub shared: &'a SharedState,
     pub sky_state: &'a mut SkyState,
     pub information_state: &'a mut InformationState,
+    pub planes_state: &'a mut PlanesState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
 }
 
 #[derive(Default)]
 pub struct TabsState {
     pub selected: Tab,
+    /// Whether to display the planes tab.
+    show_planes: bool,
+}
+
+impl TabsState {
+    /// Creates a new `TabsState` with the given configuration.
+    pub fn with_config(config: PlanesConfig) -> Self {
+        Self {
+            show_planes: config.enabled,
+            ..Self::default()
+        }
+    }
+
+    /// Applies a new configuration, keeping the selected tab if it is still
+    /// displayed.
+    pub fn reconfigure(&mut self, config: PlanesConfig) {
+        let selected = self.selected;
+        *self = Self::with_config(config);
+        self.select(selected);
+    }
+
+    /// Selects the given tab if it is displayed, keeping the selected tab
+    /// otherwise.
+    pub fn select(&mut self, tab: Tab) {
+        if self.tabs().any(|displayed| displayed == tab) {
+            self.selected = tab;
+        }
+    }
+
+    /// Returns an iterator over the displayed tabs.
+    fn tabs(&self) -> impl Iterator<Item = Tab> + '_ {
+        Tab::ALL
+            .into_iter()
+            .filter(|&tab| tab != Tab::Planes || self.show_planes)
+    }
+
+    /// Selects the tab at the given offset from the selected tab, wrapping
+    /// around.
+    fn select_relative(&mut self, offset: isize) {
+        let tabs: Vec<_> = self.tabs().collect();
+        let index = tabs
+            .iter()
+            .position(|&tab| tab == self.selected)
+            .unwrap_or(0);
+        let len = tabs.len() as isize;
+        self.selected = tabs[(index as isize + offset).rem_euclid(len) as usize];
+    }
 }
 
 impl Widget for Tabs<'_> {
@@ -76,8 +118,8 @@ impl Widget for Tabs<'_> {
 
 impl Tabs<'_> {
     fn block(&self) -> Block<'static> {
-        let mut block = Block::bordered();
-        for tab in Tab::iter() {
+        let mut block = Block::bordered().border_style(border_style(self.focused));
+        for tab in self.state.tabs() {
             if tab == self.state.selected {
                 block = block.title(tab.to_string().blue());
             } else {
@@ -93,6 +135,7 @@ impl Tabs<'_> {
                 let sky = Sky {
                     state: self.sky_state,
                     shared: self.shared,
+                    focused: self.focused,
                 };
                 sky.render(area, buf);
             }
@@ -100,30 +143,61 @@ impl Tabs<'_> {
                 let information = Information {
                     state: self.information_state,
                     shared: self.shared,
+                    focused: self.focused,
                 };
                 information.render(area, buf);
             }
+            Tab::Planes => {
+                let planes = Planes {
+                    state: self.planes_state,
+                    shared: self.shared,
+                    focused: self.focused,
+                };
+                planes.render(area, buf);
+            }
         }
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
-        Event::Key(event) => handle_key_event(event, states),
-        _ => Ok(()),
+        Event::Key(event) => Ok(handle_key_event(event, states)),
+        _ => Ok(false),
     }
 }
 
-fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<()> {
+fn handle_key_event(event: KeyEvent, states: &mut States) -> bool {
     let state = &mut states.tab_state;
 
-    if event.code == KeyCode::Tab {
-        if event.modifiers == KeyModifiers::SHIFT {
-            state.selected = state.selected.previous();
-        } else {
-            state.selected = state.selected.next();
-        }
+    if event.code != KeyCode::Tab {
+        return false;
     }
+    if event.modifiers == KeyModifiers::SHIFT {
+        state.select_relative(-1);
+    } else {
+        state.select_relative(1);
+    }
+    true
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hidden_planes_tab_is_skipped() {
+        let mut state = TabsState::with_config(PlanesConfig { enabled: false });
+        state.select_relative(1);
+        assert!(state.selected == Tab::Sky);
+        state.select_relative(1);
+        assert!(state.selected == Tab::Info);
+
+        state.reconfigure(PlanesConfig { enabled: true });
+        state.select_relative(-1);
+        assert!(state.selected == Tab::Planes);
+        state.reconfigure(PlanesConfig { enabled: false });
+        assert!(state.selected == Tab::Info);
+        state.select(Tab::Planes);
+        assert!(state.selected == Tab::Info);
+    }
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use chrono::{DateTime, Duration, Local, TimeZone, Timelike, Utc};
-use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     prelude::*,
     widgets::{
@@ -8,10 +8,18 @@ use ratatui::{
         canvas::{self, Canvas, Context},
     },
 };
+use rust_i18n::t;
 
 use crate::{
-    app::States, config::TimelineConfig, event::Event, shared_state::SharedState,
-    utils::calculate_pass_times, widgets::window_to_area,
+    app::States,
+    config::{TimeZoneMode, TimelineConfig},
+    coordinates::Lla,
+    event::Event,
+    format::format_number,
+    object::Object,
+    shared_state::SharedState,
+    utils::{Pass, calculate_pass_times},
+    widgets::{border_style, window_to_area},
 };
 
 const SECS_PER_HOUR: f64 = 3600.0;
@@ -19,6 +27,8 @@ const SECS_PER_HOUR: f64 = 3600.0;
 pub struct Timeline<'a> {
     pub state: &'a mut TimelineState,
     pub shared: &'a SharedState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
 }
 
 #[derive(Default)]
@@ -27,19 +37,105 @@ pub struct TimelineState {
     mouse_position: Option<Position>,
     /// The time step to advance or rewind when scrolling time.
     time_delta: Duration,
+    /// The NORAD ID and epoch of the object whose epoch the time was anchored
+    /// at, and the revision of the time right after.
+    epoch_anchor: Option<(u64, DateTime<Utc>, u64)>,
+    /// The frequency in Hz used to compute the doppler shift of passes.
+    doppler_frequency: Option<f64>,
+    /// The time zones in which times are displayed.
+    time_zone: TimeZoneMode,
+    /// Whether to preview the hovered time on the world map.
+    preview_on_hover: bool,
+    /// Whether to center the timeline on the next pass of a newly selected
+    /// object.
+    recenter_on_select: bool,
+    /// The NORAD ID of the object selected at the last update.
+    last_selected: Option<u64>,
+
+    /// The color of the time axis and its hour labels.
+    axis_color: Color,
+    /// The color of the hour marks on the axis.
+    hour_mark_color: Color,
+    /// The color of the current time marker.
+    current_time_color: Color,
+    /// The color of the passes and their summary.
+    pass_color: Color,
+
     /// The inner rendering area of the widget.
     inner_area: Rect,
 }
 
 impl TimelineState {
+    /// The time step used when shift is held while stepping time.
+    const LARGE_TIME_DELTA: Duration = Duration::hours(1);
+    /// The minimum pass elevations in degrees cycled through at runtime.
+    const MIN_PASS_ELEVATIONS: [f64; 5] = [0.0, 5.0, 10.0, 20.0, 30.0];
+    /// The maximum distance in columns from a pass edge a click snaps to.
+    const SNAP_COLUMNS: f64 = 2.0;
+    /// How far ahead of the current time the next pass of a newly selected
+    /// object is searched.
+    const RECENTER_LOOK_AHEAD: Duration = Duration::hours(24);
+
     /// Creates a new `TimelineState` with the given configuration.
     pub fn with_config(config: TimelineConfig) -> Self {
         Self {
             time_delta: Duration::minutes(config.time_delta_mins),
+            doppler_frequency: config.doppler_frequency_mhz.map(|mhz| mhz * 1e6),
+            time_zone: config.time_zone,
+            preview_on_hover: config.preview_on_hover,
+            recenter_on_select: config.recenter_on_select,
+            axis_color: config.axis_color,
+            hour_mark_color: config.hour_mark_color,
+            current_time_color: config.current_time_color,
+            pass_color: config.pass_color,
             ..Default::default()
         }
     }
 
+    /// Applies a new configuration, without recentering the timeline on the
+    /// selected object again.
+    pub fn reconfigure(&mut self, config: TimelineConfig) {
+        let last_selected = self.last_selected;
+        let epoch_anchor = self.epoch_anchor;
+        *self = Self::with_config(config);
+        self.last_selected = last_selected;
+        self.epoch_anchor = epoch_anchor;
+    }
+
+    /// Formats the time of day with the time part of the configured time
+    /// format in the configured time zones.
+    pub fn format_time_of_day(&self, shared: &SharedState, time: DateTime<Utc>) -> String {
+        let local = || shared.format_time_of_day(&time.with_timezone(&Local));
+        let utc = || format!("{}Z", shared.format_time_of_day(&time));
+        match self.time_zone {
+            TimeZoneMode::Local => local(),
+            TimeZoneMode::Utc => utc(),
+            TimeZoneMode::Both => format!("{}/{}", local(), utc()),
+        }
+    }
+
+    /// Returns whether the time is still anchored at the epoch of the selected
+    /// object, i.e. neither the time nor the selection changed since.
+    fn is_epoch_anchored(&self, shared: &SharedState) -> bool {
+        let Some((norad_id, epoch, revision)) = self.epoch_anchor else {
+            return false;
+        };
+        shared.time.revision() == revision
+            && shared.selected_object.as_ref().is_some_and(|selected| {
+                selected.elements().norad_id == norad_id && selected.epoch() == epoch
+            })
+    }
+
+    /// Records the NORAD ID of the selected object, returning whether the
+    /// timeline should be recentered because the selection changed.
+    fn update_selection(&mut self, selected: Option<u64>) -> bool {
+        if selected == self.last_selected {
+            return false;
+        }
+        self.last_selected = selected;
+        self.recenter_on_select
+    }
+
     fn hovered_time(&self, current_time: DateTime<Utc>) -> Option<DateTime<Utc>> {
         let mouse = self.mouse_position?;
         Some(canvas_x_to_time(
@@ -47,6 +143,23 @@ impl TimelineState {
             current_time,
         ))
     }
+
+    /// Returns the AOS or LOS closest to `time` if it is within snapping
+    /// distance, otherwise `time`.
+    fn snap_to_pass(&self, time: DateTime<Utc>, shared: &SharedState) -> DateTime<Utc> {
+        let hours_per_column = Timeline::HOURS_WINDOW as f64 / self.inner_area.width as f64;
+        let tolerance =
+            Duration::seconds((Self::SNAP_COLUMNS * hours_per_column * SECS_PER_HOUR) as i64);
+
+        shared
+            .pass_cache
+            .passes()
+            .iter()
+            .flat_map(|pass| [pass.aos, pass.los])
+            .filter(|edge| (*edge - time).abs() <= tolerance)
+            .min_by_key(|edge| (*edge - time).abs())
+            .unwrap_or(time)
+    }
 }
 
 impl Widget for Timeline<'_> {
@@ -66,51 +179,163 @@ impl Timeline<'_> {
         let current_time = self.shared.time.time();
         let mut block = Block::new()
             .borders(Borders::LEFT | Borders::RIGHT | Borders::BOTTOM)
+            .border_style(border_style(self.focused))
             .title_bottom(
                 format!(
                     "{} ({:+}m)",
-                    current_time
-                        .with_timezone(&Local)
-                        .format("%Y-%m-%d %H:%M:%S"),
+                    self.format_time(current_time),
                     self.shared.time.time_offset().num_minutes()
                 )
                 .white(),
             );
 
+        let min_elevation = self.shared.pass_cache.min_elevation();
+        if min_elevation > 0.0 {
+            block = block.title_bottom(
+                Line::from(format!(
+                    "({})",
+                    t!(
+                        "timeline-min_elevation",
+                        elevation = format_number(min_elevation, Some(0))
+                    )
+                ))
+                .fg(self.state.pass_color)
+                .centered(),
+            );
+        }
+
+        if self.shared.time.is_frozen() {
+            block = block.title_bottom(
+                Line::from(format!("({})", t!("timeline-frozen")))
+                    .light_cyan()
+                    .centered(),
+            );
+        }
+
+        if let Some(countdown) = self.countdown() {
+            block = block.title_bottom(
+                Line::from(format!("({countdown})"))
+                    .fg(self.state.pass_color)
+                    .centered(),
+            );
+        }
+
+        if self.state.is_epoch_anchored(self.shared) {
+            block = block.title_bottom(
+                Line::from(format!("({})", t!("timeline-epoch_anchored")))
+                    .yellow()
+                    .centered(),
+            );
+        }
+
         if let Some(time) = self.state.hovered_time(current_time) {
-            let label = time
-                .with_timezone(&Local)
-                .format("%Y-%m-%d %H:%M:%S")
-                .to_string();
-            block = block.title_bottom(Line::from(label).right_aligned());
+            // Show the summary of the hovered pass instead of the hovered time
+            let hovered_pass = self
+                .shared
+                .pass_cache
+                .passes()
+                .iter()
+                .find(|pass| (pass.aos..=pass.los).contains(&time));
+            let label = match hovered_pass {
+                Some(pass) => Line::from(self.pass_summary(pass)).fg(self.state.pass_color),
+                None => Line::from(self.format_time(time)),
+            };
+            block = block.title_bottom(label.right_aligned());
         }
 
         block
     }
 
+    /// Formats the time with the configured time format in the configured
+    /// time zones.
+    fn format_time(&self, time: DateTime<Utc>) -> String {
+        let local = || self.shared.format_time(&time.with_timezone(&Local));
+        match self.state.time_zone {
+            TimeZoneMode::Local => local(),
+            TimeZoneMode::Utc => format!("{} UTC", self.shared.format_time(&time)),
+            TimeZoneMode::Both => format!("{} | {} UTC", local(), self.shared.format_time(&time)),
+        }
+    }
+
+    /// Returns the countdown to the next AOS, or to the LOS if a pass is in
+    /// progress, relative to the simulation time.
+    ///
+    /// Returns `None` if no passes are predicted, i.e. without a selected
+    /// object or ground station.
+    fn countdown(&self) -> Option<String> {
+        if self.shared.selected_object.is_none() || self.shared.ground_station.is_none() {
+            return None;
+        }
+        let time = self.shared.time.time();
+        let Some(pass) = self
+            .shared
+            .pass_cache
+            .passes()
+            .iter()
+            .find(|pass| pass.los > time)
+        else {
+            return Some(t!("timeline-no_pass").into_owned());
+        };
+        Some(if pass.aos > time {
+            t!("timeline-aos_in", time = format_countdown(pass.aos - time)).into_owned()
+        } else {
+            t!("timeline-los_in", time = format_countdown(pass.los - time)).into_owned()
+        })
+    }
+
+    /// Returns a summary of the pass, including the doppler shift if a
+    /// frequency is configured.
+    fn pass_summary(&self, pass: &Pass) -> String {
+        let mut summary = t!(
+            "timeline-pass",
+            aos = self.state.format_time_of_day(self.shared, pass.aos),
+            los = self.state.format_time_of_day(self.shared, pass.los),
+            duration = (pass.los - pass.aos).num_minutes(),
+            elevation = format_number(pass.max_elevation, Some(1))
+        )
+        .into_owned();
+        summary.push_str(&t!(
+            "timeline-range",
+            approx = if pass.clipped { "~" } else { "" },
+            aos = format_number(pass.aos_range, Some(0)),
+            min = format_number(pass.max_elevation_range, Some(0)),
+            los = format_number(pass.los_range, Some(0))
+        ));
+        if let Some(frequency) = self.state.doppler_frequency {
+            let (aos_shift, los_shift) = pass.doppler_shift(frequency);
+            summary.push_str(&t!(
+                "timeline-doppler",
+                aos = format_number(aos_shift / 1e3, Some(1)),
+                los = format_number(los_shift / 1e3, Some(1))
+            ));
+        }
+        summary
+    }
+
     fn render_canvas(&self, buf: &mut Buffer) {
         Canvas::default()
+            .marker(self.shared.marker)
             .x_bounds([0.0, Self::HOURS_WINDOW as f64])
             .y_bounds([0.0, 1.0])
             .paint(|ctx| {
-                Self::draw_axis(ctx);
+                self.draw_axis(ctx);
                 ctx.layer();
                 self.draw_pass_times(ctx);
                 ctx.layer();
                 self.draw_hour_marks(ctx);
                 ctx.layer();
-                Self::draw_current_time_marker(ctx);
+                self.draw_current_time_marker(ctx);
             })
             .render(self.state.inner_area, buf);
     }
 
-    fn draw_axis(ctx: &mut Context) {
+    fn draw_axis(&self, ctx: &mut Context) {
         ctx.draw(&canvas::Line::new(
             0.0,
             0.5,
             Self::HOURS_WINDOW as f64,
             0.5,
-            Color::DarkGray,
+            self.state.axis_color,
         ));
     }
 
@@ -123,69 +348,161 @@ impl Timeline<'_> {
             let mark_time = current_time + hour_offset - minutes;
             let x = time_to_canvas_x(mark_time, current_time);
 
-            ctx.draw(&canvas::Line::new(x, 0.5, x, 0.5, Color::White));
-
-            let hours = mark_time.with_timezone(&Local).hour() % 24;
-            ctx.print(x, 0.0, format!("{hours:02}").fg(Color::DarkGray));
+            ctx.draw(&canvas::Line::new(
+                x,
+                0.5,
+                x,
+                0.5,
+                self.state.hour_mark_color,
+            ));
+
+            let hours = if self.state.time_zone == TimeZoneMode::Utc {
+                mark_time.hour()
+            } else {
+                mark_time.with_timezone(&Local).hour()
+            };
+            ctx.print(x, 0.0, format!("{hours:02}").fg(self.state.axis_color));
         }
     }
 
-    fn draw_current_time_marker(ctx: &mut Context) {
+    fn draw_current_time_marker(&self, ctx: &mut Context) {
         ctx.draw(&canvas::Line::new(
             Self::HOURS_WINDOW as f64 / 2.0,
             0.0,
             Self::HOURS_WINDOW as f64 / 2.0,
             1.0,
-            Color::LightRed,
+            self.state.current_time_color,
         ));
     }
 
     fn draw_pass_times(&self, ctx: &mut Context) {
-        let Some(selected_object) = &self.shared.selected_object else {
-            return;
-        };
-        let Some(ground_station) = &self.shared.ground_station else {
-            return;
-        };
-
         let current_time = self.shared.time.time();
-        let pass_segments = calculate_pass_times(
-            selected_object,
-            &ground_station.position,
-            &(current_time - Duration::hours(Self::HOURS_WINDOW) / 2),
-            &(current_time + Duration::hours(Self::HOURS_WINDOW) / 2),
-        );
-
-        for (start_time, end_time) in pass_segments {
-            let x1 = time_to_canvas_x(start_time, current_time).max(0.0);
-            let x2 = time_to_canvas_x(end_time, current_time).min(Self::HOURS_WINDOW as f64);
-
-            debug_assert!(x2 >= 0.0 && x1 <= Self::HOURS_WINDOW as f64);
+        let window_start = current_time - Duration::hours(Self::HOURS_WINDOW) / 2;
+        let window_end = current_time + Duration::hours(Self::HOURS_WINDOW) / 2;
+
+        for pass in self.shared.pass_cache.passes() {
+            if pass.los < window_start || pass.aos > window_end {
+                continue;
+            }
+            let x1 = time_to_canvas_x(pass.aos, current_time).max(0.0);
+            let x2 = time_to_canvas_x(pass.los, current_time).min(Self::HOURS_WINDOW as f64);
+
             ctx.draw(&canvas::Line {
                 x1,
                 y1: 0.5,
                 x2,
                 y2: 0.5,
-                color: Color::LightYellow,
+                color: self.state.pass_color,
             });
         }
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
+        Event::Update => {
+            handle_update_event(states);
+            Ok(false)
+        }
         Event::Key(event) => handle_key_event(event, states),
-        Event::Mouse(event) => handle_mouse_event(event, states),
-        _ => Ok(()),
+        Event::Mouse(event) => handle_mouse_event(event, states).map(|()| false),
+        _ => Ok(false),
     }
 }
 
-fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<()> {
-    if let KeyCode::Char('r') = event.code {
-        states.shared.time.set_time_offset(chrono::Duration::zero())
+/// Handle update events.
+fn handle_update_event(states: &mut States) {
+    let state = &mut states.timeline_state;
+    let selected = states
+        .shared
+        .selected_object
+        .as_ref()
+        .map(|object| object.elements().norad_id);
+    if !state.update_selection(selected) {
+        return;
     }
 
-    Ok(())
+    let (Some(object), Some(station)) = (
+        &states.shared.selected_object,
+        &states.shared.ground_station,
+    ) else {
+        return;
+    };
+    let Some(time) = next_pass_center(
+        object,
+        &station.position,
+        &states.shared.time.time(),
+        states.shared.pass_cache.min_elevation(),
+    ) else {
+        return;
+    };
+    states.shared.time.set_time(time);
+    // Recenter the map at the new time, rather than where auto-follow
+    // centered it at the previous time
+    states.world_map_state.follow_on_select(object, &time);
+}
+
+/// Returns the middle of the next pass of the object starting after `time`,
+/// or `None` during a pass or if there is no pass ahead.
+fn next_pass_center(
+    object: &Object,
+    station: &Lla,
+    time: &DateTime<Utc>,
+    min_elevation: f64,
+) -> Option<DateTime<Utc>> {
+    let pass = calculate_pass_times(
+        object,
+        station,
+        time,
+        &(*time + TimelineState::RECENTER_LOOK_AHEAD),
+    )
+    .into_iter()
+    .find(|pass| pass.max_elevation >= min_elevation)?;
+    (pass.aos > *time).then(|| pass.aos + (pass.los - pass.aos) / 2)
+}
+
+fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<bool> {
+    let state = &mut states.timeline_state;
+    let time = &mut states.shared.time;
+
+    // Use a larger step when shift is held
+    let step = if event.modifiers.contains(KeyModifiers::SHIFT) {
+        TimelineState::LARGE_TIME_DELTA
+    } else {
+        state.time_delta
+    };
+
+    match event.code {
+        KeyCode::Char('r') if !event.modifiers.contains(KeyModifiers::CONTROL) => {
+            time.unfreeze();
+            time.set_time_offset(Duration::zero());
+        }
+        // Anchor the time at the epoch of the selected object's elements
+        KeyCode::Char('E') => {
+            if let Some(selected) = &states.shared.selected_object {
+                time.set_time(selected.epoch());
+                state.epoch_anchor = Some((
+                    selected.elements().norad_id,
+                    selected.epoch(),
+                    time.revision(),
+                ));
+            }
+        }
+        KeyCode::Char('z') => time.toggle_frozen(),
+        KeyCode::Char('m') => {
+            let min_elevation = next_min_pass_elevation(states.shared.pass_cache.min_elevation());
+            states.shared.pass_cache.set_min_elevation(min_elevation);
+            states.toast_state.show(t!(
+                "toast-min_pass_elevation",
+                elevation = format_number(min_elevation, Some(0))
+            ));
+        }
+        KeyCode::Left | KeyCode::Char(',') => time.rewind_time(step),
+        KeyCode::Right | KeyCode::Char('.') => time.advance_time(step),
+        _ => return Ok(false),
+    }
+
+    Ok(true)
 }
 
 fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
@@ -196,6 +513,7 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     let inner_area = state.inner_area;
     let Some(local_mouse) = window_to_area(global_mouse, inner_area) else {
         state.mouse_position = None;
+        shared.preview_offset = None;
         return Ok(());
     };
     state.mouse_position = Some(local_mouse);
@@ -204,9 +522,18 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
         area_to_canvas_x(inner_area, local_mouse),
         shared.time.time(),
     );
+    if state.preview_on_hover {
+        shared.preview_offset = Some(time - shared.time.time());
+    }
 
     match event.kind {
         MouseEventKind::Down(MouseButton::Left) => {
+            // Snap to the nearest pass edge unless alt is held
+            let time = if event.modifiers.contains(KeyModifiers::ALT) {
+                time
+            } else {
+                state.snap_to_pass(time, shared)
+            };
             shared.time.set_time(time);
         }
         MouseEventKind::ScrollUp => {
@@ -221,6 +548,26 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     Ok(())
 }
 
+/// Returns the preset minimum pass elevation following `min_elevation`,
+/// wrapping around to the lowest.
+fn next_min_pass_elevation(min_elevation: f64) -> f64 {
+    TimelineState::MIN_PASS_ELEVATIONS
+        .into_iter()
+        .find(|&elevation| elevation > min_elevation)
+        .unwrap_or(TimelineState::MIN_PASS_ELEVATIONS[0])
+}
+
+/// Formats a duration as `HH:MM:SS`.
+fn format_countdown(duration: Duration) -> String {
+    let seconds = duration.num_seconds().max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
 /// Converts a position within a rectangular area to a canvas x-coordinate.
 fn area_to_canvas_x(area: Rect, position: Position) -> f64 {
     (position.x as f64 + 0.5) / area.width as f64 * Timeline::HOURS_WINDOW as f64
@@ -237,3 +584,79 @@ fn canvas_x_to_time<Tz: TimeZone>(x: f64, reference: DateTime<Tz>) -> DateTime<T
     let hours_offset = x - Timeline::HOURS_WINDOW as f64 / 2.0;
     reference + Duration::seconds((hours_offset * SECS_PER_HOUR).round() as i64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::object::test_object;
+
+    #[test]
+    fn reconfigure_does_not_recenter() {
+        let config = TimelineConfig {
+            recenter_on_select: true,
+            ..TimelineConfig::default()
+        };
+        let mut state = TimelineState::with_config(config.clone());
+        assert!(state.update_selection(Some(25544)));
+
+        state.reconfigure(config);
+        assert!(!state.update_selection(Some(25544)));
+        assert!(state.update_selection(Some(20580)));
+    }
+
+    #[test]
+    fn epoch_anchor_is_lost_when_time_or_selection_changes() {
+        let object = test_object(25544);
+        let mut shared = SharedState::default();
+        shared.selected_object = Some(object.clone());
+        shared.time.set_time(object.epoch());
+        let state = TimelineState {
+            epoch_anchor: Some((25544, object.epoch(), shared.time.revision())),
+            ..TimelineState::default()
+        };
+        assert!(state.is_epoch_anchored(&shared));
+
+        shared.time.advance_time(Duration::minutes(1));
+        assert!(!state.is_epoch_anchored(&shared));
+
+        shared.time.set_time(object.epoch());
+        let state = TimelineState {
+            epoch_anchor: Some((25544, object.epoch(), shared.time.revision())),
+            ..TimelineState::default()
+        };
+        shared.selected_object = Some(test_object(20580));
+        assert!(!state.is_epoch_anchored(&shared));
+    }
+
+    #[test]
+    fn next_pass_is_centered_after_time() {
+        let object = test_object(25544);
+        let station = Lla::new(45.0, 0.0, 0.0);
+        let time = object.epoch();
+
+        let center = next_pass_center(&object, &station, &time, 0.0).unwrap();
+        assert!(center > time);
+        let (_, el) = object.predict(&center).unwrap().position.az_el(&station);
+        assert!(el > 0.0);
+
+        // No pass reaches the zenith
+        assert!(next_pass_center(&object, &station, &time, 90.0).is_none());
+        // The time is left unchanged during a pass
+        assert!(next_pass_center(&object, &station, &center, 0.0).is_none());
+    }
+
+    #[test]
+    fn countdown_is_formatted_as_hours_minutes_seconds() {
+        assert_eq!(format_countdown(Duration::seconds(452)), "00:07:32");
+        assert_eq!(format_countdown(Duration::hours(26)), "26:00:00");
+        assert_eq!(format_countdown(Duration::seconds(-5)), "00:00:00");
+    }
+
+    #[test]
+    fn min_pass_elevation_cycles_through_presets() {
+        assert_eq!(next_min_pass_elevation(0.0), 5.0);
+        assert_eq!(next_min_pass_elevation(15.0), 20.0);
+        assert_eq!(next_min_pass_elevation(30.0), 0.0);
+        assert_eq!(next_min_pass_elevation(45.0), 0.0);
+    }
+}
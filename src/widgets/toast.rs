@@ -0,0 +1,65 @@
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Clear, Paragraph},
+};
+use unicode_width::UnicodeWidthStr;
+
+/// A widget that displays a short-lived notification message.
+pub struct Toast<'a> {
+    pub state: &'a ToastState,
+}
+
+/// State of a [`Toast`] widget.
+#[derive(Default)]
+pub struct ToastState {
+    /// The message to display and the instant it was shown.
+    message: Option<(String, Instant)>,
+}
+
+impl ToastState {
+    /// The duration for which a message is displayed.
+    const DURATION: Duration = Duration::from_secs(3);
+
+    /// Shows a message, replacing the current one.
+    pub fn show(&mut self, message: impl Into<String>) {
+        self.message = Some((message.into(), Instant::now()));
+    }
+
+    /// Dismisses the current message.
+    pub fn dismiss(&mut self) {
+        self.message = None;
+    }
+
+    /// Returns the current message if it has not expired.
+    fn message(&self) -> Option<&str> {
+        self.message
+            .as_ref()
+            .filter(|(_, instant)| instant.elapsed() < Self::DURATION)
+            .map(|(message, _)| message.as_str())
+    }
+}
+
+impl Widget for Toast<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let Some(message) = self.state.message() else {
+            return;
+        };
+
+        const BORDER_WIDTH: u16 = 1;
+        let width = (message.width() as u16 + BORDER_WIDTH * 2).min(area.width);
+        let height = (1 + BORDER_WIDTH * 2).min(area.height);
+        let popup_area = Rect::new(
+            area.x + area.width.saturating_sub(width) / 2,
+            area.y + area.height.saturating_sub(height),
+            width,
+            height,
+        );
+
+        Clear.render(popup_area, buf);
+        Paragraph::new(message)
+            .block(Block::bordered().yellow())
+            .render(popup_area, buf);
+    }
+}
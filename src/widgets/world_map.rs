@@ -1,5 +1,8 @@
+use std::{collections::VecDeque, path::Path};
+
 use anyhow::Result;
-use crossterm::event::{KeyCode, KeyEvent, MouseButton, MouseEvent, MouseEventKind};
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     prelude::*,
     style::Styled,
@@ -10,16 +13,96 @@ use ratatui::{
 };
 use rayon::prelude::*;
 use rust_i18n::t;
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::States, config::WorldMapConfig, event::Event, shared_state::SharedState, utils::*,
-    widgets::window_to_area,
+    app::States,
+    config::{LabelMode, ObjectFilterConfig, WorldMapConfig},
+    coordinates::wgs84,
+    event::Event,
+    format::{format_distance, format_number, format_offset},
+    geojson,
+    land::is_land,
+    log,
+    object::{self, Object},
+    shared_state::{SharedState, Station},
+    utils::*,
+    widgets::{border_style, window_to_area},
 };
 
 /// A widget that displays a world map with objects.
 pub struct WorldMap<'a> {
     pub state: &'a mut WorldMapState,
     pub shared: &'a SharedState,
+    /// Whether the widget has the keyboard focus.
+    pub focused: bool,
+    /// Whether to render the map without borders and titles.
+    pub clean: bool,
+}
+
+/// The view of a [`WorldMap`] widget saved in view presets.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapView {
+    /// Center longitude offset in degrees.
+    pub lon_offset: f64,
+    /// Whether the map follows the selected object.
+    pub follow_object: bool,
+    /// Whether the day-night terminator is displayed.
+    pub show_terminator: bool,
+}
+
+/// An altitude band of orbits, distinguished by color on the map.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum AltitudeBand {
+    /// Low Earth orbit, below 2,000 km.
+    Leo,
+    /// Medium Earth orbit, between LEO and GEO.
+    Meo,
+    /// Geosynchronous orbit, around 35,786 km.
+    Geo,
+    /// Beyond the geosynchronous orbit.
+    BeyondGeo,
+}
+
+impl AltitudeBand {
+    const ALL: [Self; 4] = [Self::Leo, Self::Meo, Self::Geo, Self::BeyondGeo];
+
+    /// Returns the band of the given altitude in km.
+    fn from_altitude(altitude: f64) -> Self {
+        const LEO_MAX_ALTITUDE: f64 = 2_000.0;
+        const GEO_ALTITUDE: f64 = 35_786.0;
+        const GEO_TOLERANCE: f64 = 500.0;
+
+        if altitude < LEO_MAX_ALTITUDE {
+            Self::Leo
+        } else if altitude < GEO_ALTITUDE - GEO_TOLERANCE {
+            Self::Meo
+        } else if altitude <= GEO_ALTITUDE + GEO_TOLERANCE {
+            Self::Geo
+        } else {
+            Self::BeyondGeo
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Leo => "LEO",
+            Self::Meo => "MEO",
+            Self::Geo => "GEO",
+            Self::BeyondGeo => ">GEO",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Self::Leo => Color::LightRed,
+            Self::Meo => Color::Yellow,
+            Self::Geo => Color::LightBlue,
+            Self::BeyondGeo => Color::White,
+        }
+    }
 }
 
 /// State of a [`WorldMap`] widget.
@@ -30,47 +113,323 @@ pub struct WorldMapState {
     /// The amount of longitude (in degrees) to move the map when scrolling left
     /// or right.
     lon_delta: f64,
+    /// The number of segments each drawn line is split into.
+    line_subdivisions: usize,
+    /// The maximum number of objects to render, or 0 for no limit.
+    max_rendered_objects: usize,
+    /// The maximum distance in columns of a hovered or selected object from
+    /// the cursor.
+    max_selection_distance: Option<f64>,
+    /// Which object labels are displayed.
+    labels: LabelMode,
+    /// Ranges of orbital parameters outside which objects are hidden.
+    filter: ObjectFilterConfig,
+    /// Whether to hide objects outside the filter ranges.
+    show_filter: bool,
+    /// Minimum elevation in degrees from the ground station of the objects
+    /// shown by the elevation filter.
+    filter_min_elevation_deg: f64,
+    /// Whether to hide objects below the minimum elevation from the ground
+    /// station.
+    show_elevation_filter: bool,
+    /// Objects whose name contains this pattern are highlighted, compared
+    /// case-insensitively.
+    highlight_pattern: Option<String>,
+    /// Whether to highlight objects matching the pattern.
+    show_highlight: bool,
 
     /// Whether to follow the selected object by adjusting the map longitude.
     follow_object: bool,
     /// The smoothing factor for follow mode.
     follow_smoothing: f64,
+    /// Whether to follow the objects when they are selected.
+    auto_follow_on_select: bool,
     /// Whether to display the day-night terminator line.
     show_terminator: bool,
+    /// Whether to display the edge of civil twilight and the antisolar point.
+    show_twilight: bool,
+    /// Whether to shade land areas.
+    fill_land: bool,
     /// Whether to display the visibility area.
     show_visibility_area: bool,
+    /// Minimum elevation in degrees used for the visibility area.
+    visibility_min_elevation_deg: f64,
+    /// Radius in km of the spherical Earth used for the visibility area.
+    visibility_earth_radius_km: f64,
+    /// Whether to display the eclipsed part of the ground track distinctly.
+    show_shadow_track: bool,
+    /// Whether to color the objects by altitude band.
+    show_altitude_bands: bool,
+    /// Whether to display the orbit in an inertial frame instead of the
+    /// ground track.
+    show_inertial_track: bool,
+    /// Whether to display the coordinates of the ground station.
+    show_station_coordinates: bool,
+    /// Whether to display the direction of travel of the selected object.
+    show_heading_arrow: bool,
+    /// Whether to display the azimuth and elevation of the selected object.
+    show_az_el: bool,
+    /// Whether to display the latitude band reachable by the selected object.
+    show_inclination_band: bool,
+    /// Whether to display the trail of the selected object.
+    show_trail: bool,
+    /// Whether right-clicking an object opens the context menu instead of
+    /// deselecting.
+    context_menu: bool,
+    /// The duration of the trail.
+    trail_length: Duration,
+
+    /// Recent sub-points `(time, longitude, latitude)` of the selected object,
+    /// ordered from oldest to newest.
+    trail: VecDeque<(DateTime<Utc>, f64, f64)>,
+    /// The NORAD ID of the object the trail belongs to.
+    trail_norad_id: Option<u64>,
+
+    /// Whether to draw the built-in map.
+    show_builtin_map: bool,
+    /// Lines loaded from the GeoJSON file, as `(longitude, latitude)` pairs.
+    coastlines: Vec<Vec<(f64, f64)>>,
 
     map_color: Color,
+    land_color: Color,
     trajectory_color: Color,
+    shadow_track_color: Color,
+    trail_color: Color,
     terminator_color: Color,
+    twilight_color: Color,
     visibility_area_color: Color,
+    highlight_color: Color,
+    /// Color of the reference object.
+    reference_color: Color,
+    /// Color of the objects selected with a selection rectangle.
+    multi_selection_color: Color,
 
+    /// The corners of the selection rectangle being dragged, in area
+    /// coordinates.
+    selection_drag: Option<(Position, Position)>,
     /// The inner rendering area of the widget.
     inner_area: Rect,
 }
 
 impl WorldMapState {
+    /// The step when adjusting the follow smoothing factor.
+    const FOLLOW_SMOOTHING_STEP: f64 = 0.1;
+    /// The maximum distance of an object from the cursor for right-clicking to
+    /// open its context menu.
+    const CONTEXT_MENU_DISTANCE: f64 = 2.0;
+
     /// Creates a new `WorldMapState` with the given configuration.
-    pub fn with_config(config: WorldMapConfig) -> Self {
+    ///
+    /// If `initial_center_on_station` is set and a ground station is
+    /// configured, the map is initially centered on the ground station.
+    pub fn with_config(config: WorldMapConfig, ground_station: Option<&Station>) -> Self {
+        let lon_offset = match ground_station {
+            Some(station) if config.initial_center_on_station => station.position.lon,
+            _ => config.initial_lon_offset,
+        };
         Self {
+            lon_offset: wrap_longitude_deg(lon_offset),
             follow_object: config.follow_object,
             follow_smoothing: config.follow_smoothing,
+            auto_follow_on_select: config.auto_follow_on_select,
             show_terminator: config.show_terminator,
+            show_twilight: config.show_twilight,
+            fill_land: config.fill_land,
             show_visibility_area: config.show_visibility_area,
+            visibility_min_elevation_deg: config.visibility_min_elevation_deg,
+            visibility_earth_radius_km: config
+                .visibility_earth_radius_km
+                .unwrap_or(wgs84::MEAN_RADIUS),
+            show_shadow_track: config.show_shadow_track,
+            show_inertial_track: config.show_inertial_track,
+            show_altitude_bands: config.show_altitude_bands,
+            show_trail: config.show_trail,
+            show_station_coordinates: config.show_station_coordinates,
+            show_heading_arrow: config.show_heading_arrow,
+            show_az_el: config.show_az_el,
+            show_inclination_band: config.show_inclination_band,
+            context_menu: config.context_menu,
+            trail_length: Duration::minutes(config.trail_length_mins),
             lon_delta: config.lon_delta_deg,
+            line_subdivisions: config.line_subdivisions,
+            max_rendered_objects: config.max_rendered_objects,
+            max_selection_distance: config.max_selection_distance,
+            labels: config.labels,
+            show_filter: config.filter.is_active(),
+            filter_min_elevation_deg: config.filter_min_elevation_deg,
+            show_elevation_filter: config.show_elevation_filter,
+            filter: config.filter,
+            show_highlight: config.highlight_pattern.is_some(),
+            highlight_pattern: config
+                .highlight_pattern
+                .map(|pattern| pattern.to_lowercase()),
+            show_builtin_map: config.show_builtin_map,
+            coastlines: config
+                .coastline_geojson
+                .map(|path| load_coastlines(&path))
+                .unwrap_or_default(),
             map_color: config.map_color,
+            land_color: config.land_color,
             trajectory_color: config.trajectory_color,
+            shadow_track_color: config.shadow_track_color,
+            trail_color: config.trail_color,
             terminator_color: config.terminator_color,
+            twilight_color: config.twilight_color,
             visibility_area_color: config.visibility_area_color,
+            highlight_color: config.highlight_color,
+            reference_color: config.reference_color,
+            multi_selection_color: config.multi_selection_color,
             ..Self::default()
         }
     }
 
+    /// Appends the current sub-point of the selected object to the trail.
+    ///
+    /// The trail is cleared when the selection changes or the time goes
+    /// backwards.
+    fn update_trail(&mut self, shared: &SharedState) {
+        const SAMPLE_INTERVAL: Duration = Duration::seconds(10);
+
+        let Some(selected) = &shared.selected_object else {
+            self.trail.clear();
+            self.trail_norad_id = None;
+            return;
+        };
+        let time = shared.time.time();
+        let norad_id = selected.elements().norad_id;
+        if self.trail_norad_id != Some(norad_id)
+            || self.trail.back().is_some_and(|(last, ..)| *last > time)
+        {
+            self.trail.clear();
+            self.trail_norad_id = Some(norad_id);
+        }
+
+        if self
+            .trail
+            .back()
+            .is_none_or(|(last, ..)| time - *last >= SAMPLE_INTERVAL)
+        {
+            let object_state = selected.predict(&time).unwrap();
+            self.trail
+                .push_back((time, object_state.longitude(), object_state.latitude()));
+        }
+        while self
+            .trail
+            .front()
+            .is_some_and(|(first, ..)| time - *first > self.trail_length)
+        {
+            self.trail.pop_front();
+        }
+    }
+
+    /// Returns the active highlight pattern, if any.
+    fn active_highlight_pattern(&self) -> Option<&str> {
+        self.highlight_pattern
+            .as_deref()
+            .filter(|pattern| self.show_highlight && !pattern.is_empty())
+    }
+
+    /// Applies a new configuration, keeping the current view and trail.
+    pub fn reconfigure(&mut self, config: WorldMapConfig, ground_station: Option<&Station>) {
+        *self = Self {
+            lon_offset: self.lon_offset,
+            trail: std::mem::take(&mut self.trail),
+            trail_norad_id: self.trail_norad_id,
+            ..Self::with_config(config, ground_station)
+        };
+    }
+
+    /// Returns the current view, to save in a view preset.
+    pub fn view(&self) -> MapView {
+        MapView {
+            lon_offset: self.lon_offset,
+            follow_object: self.follow_object,
+            show_terminator: self.show_terminator,
+        }
+    }
+
+    /// Restores a view saved in a view preset.
+    pub fn set_view(&mut self, view: MapView) {
+        self.lon_offset = wrap_longitude_deg(view.lon_offset);
+        self.follow_object = view.follow_object;
+        self.show_terminator = view.show_terminator;
+    }
+
+    /// Enables following the selected object.
+    pub fn enable_follow(&mut self) {
+        self.follow_object = true;
+    }
+
+    /// Follows the newly selected object if configured, centering the map on
+    /// it right away instead of smoothly panning across the map.
+    pub fn follow_on_select(&mut self, object: &Object, time: &DateTime<Utc>) {
+        if !self.auto_follow_on_select {
+            return;
+        }
+        let object_state = object.predict(time).unwrap();
+        self.lon_offset = wrap_longitude_deg(object_state.longitude());
+        self.follow_object = true;
+    }
+
+    /// Returns whether the object has not decayed and passes the active
+    /// filters.
+    ///
+    /// The elevation filter is ignored without a ground station.
+    pub fn is_visible(
+        &self,
+        object: &Object,
+        object_state: &object::State,
+        station: Option<&Station>,
+    ) -> bool {
+        if object_state.is_decayed() {
+            return false;
+        }
+        if self.show_elevation_filter
+            && let Some(station) = station
+            && object_state.position.az_el(&station.position).1 < self.filter_min_elevation_deg
+        {
+            return false;
+        }
+        if !self.show_filter {
+            return true;
+        }
+        let filter = &self.filter;
+        let inclination = object.elements().inclination;
+        filter
+            .min_altitude_km
+            .is_none_or(|min| object_state.altitude() >= min)
+            && filter
+                .max_altitude_km
+                .is_none_or(|max| object_state.altitude() <= max)
+            && filter
+                .min_inclination_deg
+                .is_none_or(|min| inclination >= min)
+            && filter
+                .max_inclination_deg
+                .is_none_or(|max| inclination <= max)
+    }
+
+    /// Returns the step between rendered objects, so that at most
+    /// `max_rendered_objects` out of `object_count` objects are rendered.
+    fn object_step(&self, object_count: usize) -> usize {
+        if self.max_rendered_objects == 0 {
+            return 1;
+        }
+        object_count.div_ceil(self.max_rendered_objects).max(1)
+    }
+
     /// Scrolls the map view to the left.
     fn scroll_map_left(&mut self) {
         self.lon_offset = wrap_longitude_deg(self.lon_offset - self.lon_delta);
     }
 
+    /// Centers the map view on the given longitude in radians and disables
+    /// follow mode.
+    fn center_on(&mut self, lon: f64) {
+        self.lon_offset = wrap_longitude_deg(lon.to_degrees());
+        self.follow_object = false;
+    }
+
     /// Scrolls the map view to the right.
     fn scroll_map_right(&mut self) {
         self.lon_offset = wrap_longitude_deg(self.lon_offset + self.lon_delta);
@@ -79,15 +438,38 @@ impl WorldMapState {
 
 impl WorldMap<'_> {
     const OBJECT_SYMBOL: &'static str = "+";
+    const FAVORITE_SYMBOL: &'static str = "★";
     const SUBSOLAR_SYMBOL: &'static str = "*";
+    const ANTISOLAR_SYMBOL: &'static str = "o";
+    /// Arrows for the 8-point compass directions, clockwise from north.
+    const HEADING_ARROWS: [&'static str; 8] = ["↑", "↗", "→", "↘", "↓", "↙", "←", "↖"];
+    const STATION_SYMBOL: &'static str = "*";
     const UNKNOWN_NAME: &'static str = "UNK";
 
     pub fn render(mut self, area: Rect, buf: &mut Buffer) {
-        let block = self.block();
+        let block = if self.clean {
+            Block::new()
+        } else {
+            self.block()
+        };
         self.state.inner_area = block.inner(area);
         block.render(area, buf);
 
         self.render_map(buf);
+
+        if let Some((start, end)) = self.state.selection_drag {
+            let inner_area = self.state.inner_area;
+            let rect = selection_rect(start, end);
+            let rect = Rect {
+                x: inner_area.x + rect.x,
+                y: inner_area.y + rect.y,
+                ..rect
+            }
+            .intersection(inner_area);
+            Block::bordered()
+                .border_style(self.state.multi_selection_color)
+                .render(rect, buf);
+        }
     }
 
     fn block(&self) -> Block<'static> {
@@ -97,25 +479,130 @@ impl WorldMap<'_> {
                 bottom_right: symbols::line::VERTICAL_LEFT,
                 ..Default::default()
             })
+            .border_style(border_style(self.focused))
             .title(t!("map-title").to_string().blue());
 
+        // Make it obvious that the positions are not the current ones
+        if !self.shared.time.is_live() {
+            let label = t!(
+                "map-time_shifted",
+                offset = format_offset(self.shared.time.time_offset())
+            );
+            block = block.title(
+                Line::from(format!(" {label} "))
+                    .black()
+                    .on_light_red()
+                    .bold()
+                    .centered(),
+            );
+        }
+
         // Show follow mode indicator if enabled
         if self.state.follow_object {
             let style = if self.shared.selected_object.is_none() {
                 Style::new().dark_gray()
             } else {
-                Style::new().green().slow_blink()
+                Style::new().green().add_modifier(self.shared.emphasis())
             };
             block = block.title_bottom(
                 Line::from(format!("({})", t!("map-follow")).set_style(style)).right_aligned(),
             );
         }
 
+        // Show the azimuth and elevation of the selected object
+        if self.state.show_az_el
+            && let Some(selected) = &self.shared.selected_object
+            && let Some(ground_station) = &self.shared.ground_station
+        {
+            let object_state = selected.predict(&self.shared.time.time()).unwrap();
+            let (az, el) = object_state.position.az_el(&ground_station.position);
+            let label = t!(
+                "map-az_el",
+                az = format_number(az, Some(1)),
+                el = format_number(el, Some(1))
+            );
+            let style = if el >= 0.0 {
+                Style::new().green()
+            } else {
+                Style::new().dark_gray()
+            };
+            block = block.title_bottom(Line::from(format!("({label})")).style(style));
+        }
+
+        // Show the active filter
+        if self.state.show_filter
+            && let Some(label) = self.state.filter.describe()
+        {
+            let label = t!("map-filter", filter = label);
+            block = block.title_bottom(Line::from(format!("({label})")).cyan());
+        }
+
+        // Show that the orbit is displayed in an inertial frame
+        if self.state.show_inertial_track && self.shared.selected_object.is_some() {
+            let label = t!("map-inertial_track");
+            block = block.title_bottom(Line::from(format!("({label})")).cyan());
+        }
+
+        // Show the active elevation filter
+        if self.state.show_elevation_filter && self.shared.ground_station.is_some() {
+            let label = t!(
+                "map-elevation_filter",
+                elevation = format_number(self.state.filter_min_elevation_deg, None)
+            );
+            block = block.title_bottom(Line::from(format!("({label})")).cyan());
+        }
+
+        // Show the active highlight pattern
+        if let Some(pattern) = self.state.active_highlight_pattern() {
+            let label = t!("map-highlight", pattern = pattern);
+            block =
+                block.title_bottom(Line::from(format!("({label})")).fg(self.state.highlight_color));
+        }
+
+        // Show the legend of the altitude bands
+        if self.state.show_altitude_bands {
+            let mut spans = vec![Span::raw("(")];
+            for (index, band) in AltitudeBand::ALL.into_iter().enumerate() {
+                if index > 0 {
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(format!("{} {}", Self::OBJECT_SYMBOL, band.label()).fg(band.color()));
+            }
+            spans.push(Span::raw(")"));
+            block = block.title_bottom(Line::from(spans));
+        }
+
+        if !self.shared.multi_selection.is_empty() {
+            let label = t!(
+                "map-multi_selection",
+                count = self.shared.multi_selection.len()
+            );
+            block = block.title_bottom(
+                Line::from(format!("({label})")).fg(self.state.multi_selection_color),
+            );
+        }
+
+        // Show the number of rendered objects if limited
+        let object_count = self.shared.objects.len();
+        let step = self.state.object_step(object_count);
+        if step > 1 {
+            let label = t!(
+                "map-objects_limited",
+                shown = object_count.div_ceil(step),
+                total = object_count
+            );
+            block = block.title_bottom(Line::from(format!("({label})").yellow()));
+        }
+
         block
     }
 
     /// Renders the world map.
     fn render_map(&mut self, buf: &mut Buffer) {
+        if self.state.show_trail {
+            self.state.update_trail(self.shared);
+        }
+
         // Follow the longitude of the selected object
         if self.state.follow_object
             && let Some(selected) = &self.shared.selected_object
@@ -150,23 +637,53 @@ impl WorldMap<'_> {
         for bounds in &bounds_vec {
             self.render_top_layer(buf, *bounds);
         }
+
+        if self.state.fill_land {
+            self.render_land(buf);
+        }
+    }
+
+    /// Shades the background of cells on land.
+    fn render_land(&self, buf: &mut Buffer) {
+        let area = self.state.inner_area;
+        for y in 0..area.height {
+            let lat = 90.0 - (y as f64 + 0.5) / area.height as f64 * 180.0;
+            for x in 0..area.width {
+                let lon = wrap_longitude_deg(
+                    self.state.lon_offset - 180.0 + (x as f64 + 0.5) / area.width as f64 * 360.0,
+                );
+                if is_land(lon, lat) {
+                    buf[(area.x + x, area.y + y)].set_bg(self.state.land_color);
+                }
+            }
+        }
     }
 
     /// Renders the bottom layer of the world map, including the map and all
     /// objects.
     fn render_bottom_layer(&self, buf: &mut Buffer, x_bounds: [f64; 2]) {
         Canvas::default()
+            .marker(self.shared.marker)
             .x_bounds(x_bounds)
             .y_bounds([-90.0, 90.0])
             .paint(|ctx| {
-                ctx.draw(&Map {
-                    color: self.state.map_color,
-                    resolution: MapResolution::High,
-                });
+                if self.state.show_builtin_map {
+                    ctx.draw(&Map {
+                        color: self.state.map_color,
+                        resolution: MapResolution::High,
+                    });
+                }
+                self.draw_coastlines(ctx);
                 ctx.layer();
                 if self.state.show_terminator {
                     self.draw_terminator(ctx);
                 }
+                if self.state.show_twilight {
+                    self.draw_twilight(ctx);
+                }
+                if self.state.show_inclination_band {
+                    self.draw_inclination_band(ctx);
+                }
                 self.draw_objects(ctx);
             })
             .render(self.state.inner_area, buf);
@@ -176,9 +693,16 @@ impl WorldMap<'_> {
     /// trajectories.
     fn render_top_layer(&self, buf: &mut Buffer, x_bounds: [f64; 2]) {
         Canvas::default()
+            .marker(self.shared.marker)
             .x_bounds(x_bounds)
             .y_bounds([-90.0, 90.0])
             .paint(|ctx| {
+                if self.state.show_trail {
+                    self.draw_trail(ctx);
+                }
+                self.draw_multi_selection(ctx);
+                self.draw_favorites(ctx);
+                self.draw_reference(ctx);
                 self.draw_object_highlight(ctx);
                 if self.state.show_visibility_area {
                     self.draw_visibility_area(ctx);
@@ -188,10 +712,21 @@ impl WorldMap<'_> {
             .render(self.state.inner_area, buf);
     }
 
+    /// Draws the lines loaded from the GeoJSON file.
+    ///
+    /// The lines are usually dense, so they are drawn straight.
+    fn draw_coastlines(&self, ctx: &mut Context) {
+        for line in &self.state.coastlines {
+            for window in line.windows(2) {
+                Self::draw_line_segment(ctx, window[0], window[1], self.state.map_color);
+            }
+        }
+    }
+
     /// Draws the day-night terminator and subsolar point.
     fn draw_terminator(&self, ctx: &mut Context) {
         // Draw the terminator line
-        Self::draw_lines(
+        self.draw_lines(
             ctx,
             calculate_terminator(&self.shared.time.time()),
             self.state.terminator_color,
@@ -206,22 +741,108 @@ impl WorldMap<'_> {
         );
     }
 
+    /// Draws the latitude band the ground track of the selected object can
+    /// reach.
+    fn draw_inclination_band(&self, ctx: &mut Context) {
+        let Some(selected) = &self.shared.selected_object else {
+            return;
+        };
+
+        // Retrograde orbits reach the same latitude as their supplement
+        let inclination = selected.elements().inclination;
+        let max_lat = if inclination > 90.0 {
+            180.0 - inclination
+        } else {
+            inclination
+        };
+        for lat in [max_lat, -max_lat] {
+            ctx.draw(&canvas::Line::new(-180.0, lat, 180.0, lat, Color::DarkGray));
+        }
+    }
+
+    /// Draws the edge of civil twilight and the antisolar point.
+    fn draw_twilight(&self, ctx: &mut Context) {
+        const CIVIL_TWILIGHT_DEG: f64 = 6.0;
+
+        let time = self.shared.time.time();
+        self.draw_lines(
+            ctx,
+            calculate_twilight(&time, CIVIL_TWILIGHT_DEG),
+            self.state.twilight_color,
+        );
+
+        let (lon, lat) = antisolar_point(&time);
+        ctx.print(
+            lon.to_degrees(),
+            lat.to_degrees(),
+            Self::ANTISOLAR_SYMBOL.fg(self.state.twilight_color),
+        );
+    }
+
     /// Draws all objects and their labels.
     fn draw_objects(&self, ctx: &mut Context) {
         let time = self.shared.time.time();
+        let step = self.state.object_step(self.shared.objects.len());
+        let highlight_pattern = self.state.active_highlight_pattern();
 
         for (text, state) in self
             .shared
             .objects
             .par_iter()
-            .map(|object| {
+            .step_by(step)
+            .filter_map(|object| {
+                // Favorites are drawn on the top layer
+                if self.shared.favorites.contains(&object.elements().norad_id) {
+                    return None;
+                }
+                let object_state = object.predict(&time).unwrap();
+                if !self.state.is_visible(
+                    object,
+                    &object_state,
+                    self.shared.ground_station.as_ref(),
+                ) {
+                    return None;
+                }
+
                 let object_name = object.name().unwrap_or(Self::UNKNOWN_NAME);
-                let text = if self.shared.selected_object.is_none() {
-                    Self::OBJECT_SYMBOL.light_red() + format!(" {object_name}").white()
+                let is_highlighted = highlight_pattern
+                    .is_some_and(|pattern| object_name.to_lowercase().contains(pattern));
+                let (symbol, label) = if is_highlighted {
+                    (
+                        Self::OBJECT_SYMBOL.fg(self.state.highlight_color).bold(),
+                        format!(" {object_name}").fg(self.state.highlight_color),
+                    )
+                } else if self.state.show_altitude_bands {
+                    let color = AltitudeBand::from_altitude(object_state.altitude()).color();
+                    let mut symbol = Self::OBJECT_SYMBOL.fg(color);
+                    let mut label = format!(" {object_name}").white();
+                    if self.shared.selected_object.is_some() {
+                        symbol = symbol.dim();
+                        label = label.dark_gray();
+                    }
+                    (symbol, label)
+                } else if self.shared.selected_object.is_none() {
+                    (
+                        Self::OBJECT_SYMBOL.light_red(),
+                        format!(" {object_name}").white(),
+                    )
+                } else {
+                    (
+                        Self::OBJECT_SYMBOL.red(),
+                        format!(" {object_name}").dark_gray(),
+                    )
+                };
+                let show_label = match self.state.labels {
+                    LabelMode::All => true,
+                    LabelMode::SelectedOnly => is_highlighted,
+                    LabelMode::None => false,
+                };
+                let text = if show_label {
+                    symbol + label
                 } else {
-                    Self::OBJECT_SYMBOL.red() + format!(" {object_name}").dark_gray()
+                    Line::from(symbol)
                 };
-                (text, object.predict(&time).unwrap())
+                Some((text, object_state))
             })
             .collect::<Vec<_>>()
         {
@@ -229,21 +850,96 @@ impl WorldMap<'_> {
         }
     }
 
+    /// Draws the favorite objects and their labels.
+    fn draw_favorites(&self, ctx: &mut Context) {
+        let time = self.shared.time.time();
+        for object in self
+            .shared
+            .objects
+            .iter()
+            .filter(|object| self.shared.favorites.contains(&object.elements().norad_id))
+        {
+            let object_name = object.name().unwrap_or(Self::UNKNOWN_NAME);
+            let text = Self::FAVORITE_SYMBOL.light_yellow().bold()
+                + format!(" {object_name}").light_yellow();
+            let object_state = object.predict(&time).unwrap();
+            ctx.print(object_state.longitude(), object_state.latitude(), text);
+        }
+    }
+
+    /// Draws the trail of the selected object, fading out older points.
+    fn draw_trail(&self, ctx: &mut Context) {
+        let colors = [Color::DarkGray, Color::Gray, self.state.trail_color];
+        let time = self.shared.time.time();
+        let trail_secs = self.state.trail_length.as_seconds_f64().max(1.0);
+
+        for (start, end) in self.state.trail.iter().zip(self.state.trail.iter().skip(1)) {
+            let age = (time - start.0).as_seconds_f64() / trail_secs;
+            let index = ((1.0 - age) * colors.len() as f64) as usize;
+            let color = colors[index.min(colors.len() - 1)];
+            self.draw_line(ctx, (start.1, start.2), (end.1, end.2), color);
+        }
+    }
+
     /// Draws the highlight and trajectory for the selected or hovered object.
     fn draw_object_highlight(&self, ctx: &mut Context) {
         if let Some(selected) = &self.shared.selected_object {
-            // Draw the trajectory
-            Self::draw_lines(
-                ctx,
-                calculate_ground_track(selected, &self.shared.time.time()),
-                self.state.trajectory_color,
-            );
+            let object_state = selected.predict(&self.shared.time.time()).unwrap();
+
+            // Draw the trajectory, except for geostationary objects whose
+            // ground track collapses to a dot and decayed objects
+            let is_geostationary = selected.is_geostationary();
+            let is_decayed = object_state.is_decayed();
+            if (self.state.show_inertial_track || !is_geostationary) && !is_decayed {
+                let points = if self.state.show_inertial_track {
+                    calculate_inertial_track(selected, &self.shared.time.time())
+                } else {
+                    calculate_ground_track(selected, &self.shared.time.time())
+                };
+                for window in points.windows(2) {
+                    let (x1, y1, is_sunlit) = window[0];
+                    let (x2, y2, _) = window[1];
+                    let color = if self.state.show_shadow_track && !is_sunlit {
+                        self.state.shadow_track_color
+                    } else {
+                        self.state.trajectory_color
+                    };
+                    self.draw_line(ctx, (x1, y1), (x2, y2), color);
+                }
+            }
 
             // Highlight the selected object
             let object_name = selected.name().unwrap_or(Self::UNKNOWN_NAME);
-            let text =
-                Self::OBJECT_SYMBOL.light_green().slow_blink() + format!(" {object_name}").white();
-            let object_state = selected.predict(&self.shared.time.time()).unwrap();
+            let mut label = format!(" {object_name}");
+            if is_decayed {
+                label += &format!(" ({})", t!("map-decayed"));
+            } else if is_geostationary {
+                let longitude = format_longitude(object_state.longitude());
+                label += &format!(" ({})", t!("map-geostationary", lon = longitude));
+            } else if selected.is_geosynchronous() {
+                label += &format!(" ({})", t!("map-geosynchronous"));
+            }
+            if self.state.show_heading_arrow && !is_decayed && !is_geostationary {
+                let heading = selected.heading(&self.shared.time.time()).unwrap();
+                label += &format!(" {}", Self::HEADING_ARROWS[compass_point(heading)]);
+            }
+            let text = Self::OBJECT_SYMBOL
+                .light_green()
+                .add_modifier(self.shared.emphasis())
+                + label.white();
+
+            // Mark where the object will be at the time previewed on the
+            // timeline
+            if let Some(offset) = self.shared.preview_offset
+                && !is_decayed
+            {
+                let preview_state = selected
+                    .predict(&(self.shared.time.time() + offset))
+                    .unwrap();
+                let text = Self::OBJECT_SYMBOL.green().dim()
+                    + format!(" {:+}m", offset.num_minutes()).dark_gray();
+                ctx.print(preview_state.longitude(), preview_state.latitude(), text);
+            }
             ctx.print(object_state.longitude(), object_state.latitude(), text);
         } else if let Some(hovered) = &self.shared.hovered_object {
             // Highlight the hovered object
@@ -256,36 +952,135 @@ impl WorldMap<'_> {
         }
     }
 
+    /// Draws the reference object compared with the selected object.
+    fn draw_reference(&self, ctx: &mut Context) {
+        let Some(reference) = &self.shared.reference_object else {
+            return;
+        };
+        let object_state = reference.predict(&self.shared.time.time()).unwrap();
+        let object_name = reference.name().unwrap_or(Self::UNKNOWN_NAME);
+        let text = Self::OBJECT_SYMBOL.fg(self.state.reference_color).bold()
+            + format!(" {object_name}").fg(self.state.reference_color);
+        ctx.print(object_state.longitude(), object_state.latitude(), text);
+    }
+
+    /// Draws the objects selected with a selection rectangle.
+    fn draw_multi_selection(&self, ctx: &mut Context) {
+        let time = self.shared.time.time();
+        for object in self.shared.objects.iter().filter(|object| {
+            self.shared
+                .multi_selection
+                .contains(&object.elements().norad_id)
+        }) {
+            let object_state = object.predict(&time).unwrap();
+            ctx.print(
+                object_state.longitude(),
+                object_state.latitude(),
+                Self::OBJECT_SYMBOL
+                    .fg(self.state.multi_selection_color)
+                    .bold(),
+            );
+        }
+    }
+
     /// Draws the visibility area for the selected object.
     fn draw_visibility_area(&self, ctx: &mut Context) {
         let Some(object) = &self.shared.selected_object else {
             return;
         };
         let object_state = object.predict(&self.shared.time.time()).unwrap();
-        let points = calculate_visibility_area(&object_state.position);
-        Self::draw_lines(ctx, points, self.state.visibility_area_color);
+        if object_state.is_decayed() {
+            return;
+        }
+        let points = calculate_visibility_area(
+            &object_state.position,
+            self.state.visibility_min_elevation_deg,
+            self.state.visibility_earth_radius_km,
+        );
+        self.draw_lines(ctx, points, self.state.visibility_area_color);
     }
 
     fn draw_ground_station(&self, ctx: &mut Context) {
         let Some(ground_station) = &self.shared.ground_station else {
             return;
         };
-        ctx.print(
-            ground_station.position.lon,
-            ground_station.position.lat,
-            "*".light_cyan().bold() + format!(" {}", ground_station.name).light_cyan(),
-        );
+        let position = &ground_station.position;
+
+        let mut labels = vec![ground_station.name.clone()];
+        if self.state.show_station_coordinates {
+            labels.push(format!(
+                "{} {}",
+                format_lat_lon(position.lat, position.lon),
+                format_distance(position.alt, false)
+            ));
+        }
+        let label_width = labels.iter().map(|label| label.width()).max().unwrap_or(0);
+
+        // Place the labels on the left of the marker if they would overflow the
+        // right edge of the map
+        let area = self.state.inner_area;
+        let cell_width = 360.0 / area.width.max(1) as f64;
+        let cell_height = 180.0 / area.height.max(1) as f64;
+        let marker_x = wrap_longitude_deg(position.lon - self.state.lon_offset) + 180.0;
+        let on_left = marker_x + (label_width + 2) as f64 * cell_width > 360.0;
+
+        let marker = Self::STATION_SYMBOL.light_cyan().bold();
+        for (i, label) in labels.into_iter().enumerate() {
+            // Print the following lines below the marker, or above it at the
+            // bottom of the map
+            let mut y = position.lat - i as f64 * cell_height;
+            if y < -90.0 {
+                y = position.lat + i as f64 * cell_height;
+            }
+            let (x, line) = match (i, on_left) {
+                (0, false) => (
+                    position.lon,
+                    marker.clone() + format!(" {label}").light_cyan(),
+                ),
+                (0, true) => (
+                    position.lon - (label_width + 1) as f64 * cell_width,
+                    format!("{label:>label_width$} ").light_cyan() + marker.clone(),
+                ),
+                (_, false) => (position.lon + 2.0 * cell_width, label.light_cyan().into()),
+                (_, true) => (
+                    position.lon - (label_width + 1) as f64 * cell_width,
+                    format!("{label:>label_width$}").light_cyan().into(),
+                ),
+            };
+            ctx.print(x, y, line);
+        }
     }
 
     /// Draws lines between points.
-    fn draw_lines(ctx: &mut Context, points: Vec<(f64, f64)>, color: Color) {
+    fn draw_lines(&self, ctx: &mut Context, points: Vec<(f64, f64)>, color: Color) {
         for window in points.windows(2) {
-            Self::draw_line(ctx, window[0], window[1], color);
+            self.draw_line(ctx, window[0], window[1], color);
+        }
+    }
+
+    /// Draws a line between two points along the great circle connecting them.
+    fn draw_line(&self, ctx: &mut Context, from: (f64, f64), to: (f64, f64), color: Color) {
+        let subdivisions = self.state.line_subdivisions.max(1);
+        let mut start = from;
+        for i in 1..=subdivisions {
+            let end = if i == subdivisions {
+                to
+            } else {
+                let (lon, lat) = interpolate_great_circle(from, to, i as f64 / subdivisions as f64);
+                (wrap_longitude_deg(lon), lat)
+            };
+            Self::draw_line_segment(ctx, start, end, color);
+            start = end;
         }
     }
 
-    /// Draws a line between two points.
-    fn draw_line(ctx: &mut Context, (x1, y1): (f64, f64), (x2, y2): (f64, f64), color: Color) {
+    /// Draws a straight line between two points.
+    fn draw_line_segment(
+        ctx: &mut Context,
+        (x1, y1): (f64, f64),
+        (x2, y2): (f64, f64),
+        color: Color,
+    ) {
         // Handle trajectory crossing the international date line
         if (x1 - x2).abs() >= 180.0 {
             let x_edge = if x1 > 0.0 { 180.0 } else { -180.0 };
@@ -298,46 +1093,201 @@ impl WorldMap<'_> {
     }
 }
 
-pub fn handle_event(event: Event, states: &mut States) -> Result<()> {
+/// Loads the lines of the GeoJSON file, logging failures.
+fn load_coastlines(path: &Path) -> Vec<Vec<(f64, f64)>> {
+    match geojson::load_lines(path) {
+        Ok(lines) => {
+            log::push(format!(
+                "Loaded {} lines from {}",
+                lines.len(),
+                path.display()
+            ));
+            lines
+        }
+        Err(e) => {
+            log::push(format!("Failed to load {}: {e}", path.display()));
+            Vec::new()
+        }
+    }
+}
+
+/// Formats a latitude and longitude compactly, e.g. `48.1°N 11.6°E`.
+fn format_lat_lon(lat: f64, lon: f64) -> String {
+    let lat_hemisphere = if lat >= 0.0 { 'N' } else { 'S' };
+    format!(
+        "{:.1}°{lat_hemisphere} {}",
+        lat.abs(),
+        format_longitude(lon)
+    )
+}
+
+/// Formats a longitude compactly, e.g. `11.6°E`.
+fn format_longitude(lon: f64) -> String {
+    let hemisphere = if lon >= 0.0 { 'E' } else { 'W' };
+    format!("{:.1}°{hemisphere}", lon.abs())
+}
+
+pub fn handle_event(event: Event, states: &mut States) -> Result<bool> {
     match event {
         Event::Key(event) => handle_key_event(event, states),
-        Event::Mouse(event) => handle_mouse_event(event, states),
-        _ => Ok(()),
+        Event::Mouse(event) => handle_mouse_event(event, states).map(|()| false),
+        _ => Ok(false),
     }
 }
 
-fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<()> {
+fn handle_key_event(event: KeyEvent, states: &mut States) -> Result<bool> {
     match event.code {
         KeyCode::Char('[') => states.world_map_state.scroll_map_left(),
         KeyCode::Char(']') => states.world_map_state.scroll_map_right(),
         KeyCode::Char('f') => {
             states.world_map_state.follow_object = !states.world_map_state.follow_object;
         }
+        KeyCode::Char('+') | KeyCode::Char('=') | KeyCode::Char('-') => {
+            let state = &mut states.world_map_state;
+            let step = if event.code == KeyCode::Char('-') {
+                -WorldMapState::FOLLOW_SMOOTHING_STEP
+            } else {
+                WorldMapState::FOLLOW_SMOOTHING_STEP
+            };
+            // Round to avoid accumulating floating-point errors
+            state.follow_smoothing =
+                ((state.follow_smoothing + step).clamp(0.0, 1.0) * 10.0).round() / 10.0;
+            states.toast_state.show(t!(
+                "toast-follow_smoothing",
+                value = format_number(state.follow_smoothing, Some(1))
+            ));
+        }
+        KeyCode::Char('n') | KeyCode::Char('N') => {
+            // Center on the subsolar point, or on the antisolar point with shift
+            let time = states.shared.time.time();
+            let (lon, key) = if event.code == KeyCode::Char('n') {
+                (subsolar_point(&time).0, "toast-centered_subsolar")
+            } else {
+                (antisolar_point(&time).0, "toast-centered_antisolar")
+            };
+            states.world_map_state.center_on(lon);
+            let lon = format_longitude(states.world_map_state.lon_offset);
+            states.toast_state.show(t!(key, lon = lon));
+        }
         KeyCode::Char('t') => {
             states.world_map_state.show_terminator = !states.world_map_state.show_terminator;
         }
-        _ => {}
+        KeyCode::Char('T') => {
+            states.world_map_state.show_twilight = !states.world_map_state.show_twilight;
+        }
+        KeyCode::Char('v') => {
+            states.world_map_state.show_visibility_area =
+                !states.world_map_state.show_visibility_area;
+        }
+        KeyCode::Char('c') => {
+            states.world_map_state.fill_land = !states.world_map_state.fill_land;
+        }
+        KeyCode::Char('x') => {
+            states.world_map_state.show_filter = !states.world_map_state.show_filter;
+        }
+        KeyCode::Char('A') => {
+            states.world_map_state.show_altitude_bands =
+                !states.world_map_state.show_altitude_bands;
+        }
+        KeyCode::Char('I') => {
+            states.world_map_state.show_inertial_track =
+                !states.world_map_state.show_inertial_track;
+        }
+        KeyCode::Char('V') => {
+            states.world_map_state.show_elevation_filter =
+                !states.world_map_state.show_elevation_filter;
+        }
+        KeyCode::Char('l') => {
+            states.world_map_state.labels = states.world_map_state.labels.next();
+        }
+        KeyCode::Char('h') => {
+            states.world_map_state.show_highlight = !states.world_map_state.show_highlight;
+        }
+        KeyCode::Char('i') => {
+            states.world_map_state.show_inclination_band =
+                !states.world_map_state.show_inclination_band;
+        }
+        KeyCode::Char('e') => {
+            states.world_map_state.show_shadow_track = !states.world_map_state.show_shadow_track;
+        }
+        _ => return Ok(false),
     }
 
-    Ok(())
+    Ok(true)
 }
 
 fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     let global_mouse = Position::new(event.column, event.row);
     let inner_area = states.world_map_state.inner_area;
+
+    // Keep dragging the selection rectangle when the mouse leaves the map
+    if let Some((start, _)) = states.world_map_state.selection_drag
+        && !inner_area.is_empty()
+    {
+        let end = Position::new(
+            global_mouse
+                .x
+                .clamp(inner_area.left(), inner_area.right() - 1)
+                - inner_area.x,
+            global_mouse
+                .y
+                .clamp(inner_area.top(), inner_area.bottom() - 1)
+                - inner_area.y,
+        );
+        match event.kind {
+            MouseEventKind::Drag(MouseButton::Left) => {
+                states.world_map_state.selection_drag = Some((start, end));
+                return Ok(());
+            }
+            MouseEventKind::Up(MouseButton::Left) => {
+                states.world_map_state.selection_drag = None;
+                let rect = selection_rect(start, end);
+                states.shared.multi_selection = get_object_positions(states, inner_area)
+                    .into_iter()
+                    .filter(|&(_, (x, y))| rect.contains(Position::new(x, y)))
+                    .map(|(index, _)| states.shared.objects[index].elements().norad_id)
+                    .collect();
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
     let Some(local_mouse) = window_to_area(global_mouse, inner_area) else {
         states.shared.hovered_object = None;
         return Ok(());
     };
 
-    let nearest_object_index = get_nearest_object_index(states, local_mouse, inner_area);
+    let nearest_object = get_nearest_object(states, local_mouse, inner_area);
+    let nearest_object_index = nearest_object.map(|(index, _)| index);
     match event.kind {
+        MouseEventKind::Down(MouseButton::Left)
+            if event.modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            states.world_map_state.selection_drag = Some((local_mouse, local_mouse));
+        }
         MouseEventKind::Down(MouseButton::Left) => {
             states.shared.selected_object =
                 nearest_object_index.map(|index| states.shared.objects[index].clone());
+            if let Some(selected) = &states.shared.selected_object {
+                states
+                    .world_map_state
+                    .follow_on_select(selected, &states.shared.time.time());
+            }
         }
         MouseEventKind::Down(MouseButton::Right) => {
-            states.shared.selected_object = None;
+            states.shared.multi_selection.clear();
+            // Open the context menu over an object, otherwise deselect
+            match nearest_object {
+                Some((index, distance))
+                    if states.world_map_state.context_menu
+                        && distance <= WorldMapState::CONTEXT_MENU_DISTANCE =>
+                {
+                    let object = states.shared.objects[index].clone();
+                    states.context_menu_state.open(object, global_mouse);
+                }
+                _ => states.shared.selected_object = None,
+            }
         }
         MouseEventKind::ScrollUp => {
             states.world_map_state.scroll_map_left();
@@ -353,30 +1303,86 @@ fn handle_mouse_event(event: MouseEvent, states: &mut States) -> Result<()> {
     Ok(())
 }
 
-/// Get the index of the nearest object to the given area position.
-fn get_nearest_object_index(
+/// Get the index of the nearest object to the given area position and its
+/// distance.
+fn get_nearest_object(
     states: &States,
     position: Position,
     inner_area: Rect,
-) -> Option<usize> {
+) -> Option<(usize, f64)> {
+    nearest_position(
+        get_object_positions(states, inner_area),
+        position,
+        states.world_map_state.max_selection_distance,
+    )
+}
+
+/// Returns the index of the position nearest to `position` and its distance,
+/// ignoring the positions farther than `max_distance`.
+fn nearest_position(
+    positions: impl IntoIterator<Item = (usize, (u16, u16))>,
+    position: Position,
+    max_distance: Option<f64>,
+) -> Option<(usize, f64)> {
+    positions
+        .into_iter()
+        .map(|(index, (x, y))| (index, screen_distance((x, y), position)))
+        .filter(|(_, distance)| max_distance.is_none_or(|max| *distance <= max))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+/// Returns the Euclidean distance on screen between two area positions, in
+/// columns.
+///
+/// Terminal cells are about twice as high as wide, so a row counts as two
+/// columns.
+fn screen_distance((x, y): (u16, u16), position: Position) -> f64 {
+    let dx = x as f64 - position.x as f64;
+    let dy = (y as f64 - position.y as f64) * 2.0;
+    dx.hypot(dy)
+}
+
+/// Get the indices of the rendered objects and their area positions.
+fn get_object_positions(states: &States, inner_area: Rect) -> Vec<(usize, (u16, u16))> {
     let time = states.shared.time.time();
+    let step = states
+        .world_map_state
+        .object_step(states.shared.objects.len());
+    let favorites = &states.shared.favorites;
 
     states
         .shared
         .objects
         .par_iter()
         .enumerate()
-        .min_by_key(|(_, obj)| {
+        .filter(|(index, obj)| {
+            // Favorites are always rendered, and thus always selectable
+            index % step == 0 || favorites.contains(&obj.elements().norad_id)
+        })
+        .filter_map(|(index, obj)| {
             let state = obj.predict(&time).unwrap();
+            if !states.world_map_state.is_visible(
+                obj,
+                &state,
+                states.shared.ground_station.as_ref(),
+            ) {
+                return None;
+            }
             // Convert to area position
-            let (x, y) = lon_lat_to_area(
+            let position = lon_lat_to_area(
                 wrap_longitude_deg(state.longitude() - states.world_map_state.lon_offset),
                 state.latitude(),
                 inner_area,
             );
-            (x as i32 - position.x as i32).abs() + (y as i32 - position.y as i32).abs() * 2
+            Some((index, position))
         })
-        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Returns the rectangle spanned by two corners, in area coordinates.
+fn selection_rect(start: Position, end: Position) -> Rect {
+    let (x, y) = (start.x.min(end.x), start.y.min(end.y));
+    Rect::new(x, y, start.x.max(end.x) - x + 1, start.y.max(end.y) - y + 1)
 }
 
 #[expect(dead_code)]
@@ -401,3 +1407,130 @@ fn lon_lat_to_area(lon: f64, lat: f64, area: Rect) -> (u16, u16) {
     let y = ((90.0 - lat) * area.height as f64 / 180.0) - 1.0;
     (x.round() as u16, y.round() as u16)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinates::{Lla, Teme},
+        object::test_object,
+    };
+
+    /// Returns the area position of the given coordinates on a map centered on
+    /// `lon_offset`.
+    fn project(lon: f64, lat: f64, lon_offset: f64, area: Rect) -> (u16, u16) {
+        lon_lat_to_area(wrap_longitude_deg(lon - lon_offset), lat, area)
+    }
+
+    #[test]
+    fn nearest_object_uses_screen_distance_near_poles() {
+        let area = Rect::new(0, 0, 120, 60);
+        // Three columns and one row away, against two rows away
+        let positions = [
+            (0, project(9.0, 81.0, 0.0, area)),
+            (1, project(0.0, 78.0, 0.0, area)),
+        ];
+        let cursor = project(0.0, 84.0, 0.0, area);
+        let cursor = Position::new(cursor.0, cursor.1);
+        let (index, distance) = nearest_position(positions, cursor, None).unwrap();
+        assert_eq!(index, 0);
+        assert!((distance - 13f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn nearest_object_across_antimeridian() {
+        let area = Rect::new(0, 0, 120, 60);
+        // Objects on either side of the antimeridian are at opposite edges of
+        // a map centered on the prime meridian, and adjacent when centered on
+        // the antimeridian
+        let positions = [
+            (0, project(179.0, 0.0, 0.0, area)),
+            (1, project(-170.0, 0.0, 0.0, area)),
+        ];
+        let cursor = Position::new(area.width - 1, 29);
+        assert_eq!(nearest_position(positions, cursor, None).unwrap().0, 0);
+
+        let positions = [
+            (0, project(177.0, 0.0, 180.0, area)),
+            (1, project(-176.0, 0.0, 180.0, area)),
+        ];
+        let cursor = project(-178.0, 0.0, 180.0, area);
+        let cursor = Position::new(cursor.0, cursor.1);
+        assert_eq!(nearest_position(positions, cursor, None).unwrap().0, 1);
+    }
+
+    #[test]
+    fn far_objects_are_not_selected() {
+        let positions = [(0, (10, 10))];
+        assert!(nearest_position(positions, Position::new(13, 10), Some(2.0)).is_none());
+        assert_eq!(
+            nearest_position(positions, Position::new(12, 10), Some(2.0)),
+            Some((0, 2.0))
+        );
+    }
+
+    #[test]
+    fn altitude_bands_split_common_orbits() {
+        assert_eq!(AltitudeBand::from_altitude(420.0), AltitudeBand::Leo);
+        assert_eq!(AltitudeBand::from_altitude(20_200.0), AltitudeBand::Meo);
+        assert_eq!(AltitudeBand::from_altitude(35_786.0), AltitudeBand::Geo);
+        assert_eq!(
+            AltitudeBand::from_altitude(70_000.0),
+            AltitudeBand::BeyondGeo
+        );
+    }
+
+    #[test]
+    fn decayed_objects_are_hidden() {
+        let object = test_object(25544);
+        let state = |alt| object::State {
+            position: Lla::new(10.0, 20.0, alt),
+            velocity: Teme::new(0.0, 7.6, 0.0),
+        };
+        let decayed = state(-10.0);
+        assert!(decayed.is_decayed());
+        assert!(!state(400.0).is_decayed());
+
+        let map_state = WorldMapState::default();
+        assert!(!map_state.is_visible(&object, &decayed, None));
+        assert!(map_state.is_visible(&object, &state(400.0), None));
+    }
+
+    #[test]
+    fn auto_follow_on_select_centers_on_object() {
+        let object = test_object(25544);
+        let time = object.epoch();
+        let longitude = object.predict(&time).unwrap().longitude();
+
+        let mut state = WorldMapState::with_config(
+            WorldMapConfig {
+                follow_object: false,
+                auto_follow_on_select: true,
+                ..WorldMapConfig::default()
+            },
+            None,
+        );
+        state.follow_on_select(&object, &time);
+        assert!(state.follow_object);
+        assert!((state.lon_offset - wrap_longitude_deg(longitude)).abs() < 1e-9);
+
+        let mut state = WorldMapState::with_config(
+            WorldMapConfig {
+                follow_object: false,
+                auto_follow_on_select: false,
+                ..WorldMapConfig::default()
+            },
+            None,
+        );
+        state.follow_on_select(&object, &time);
+        assert!(!state.follow_object);
+        assert_eq!(state.lon_offset, 0.0);
+    }
+
+    #[test]
+    fn selection_rect_spans_both_corners() {
+        let rect = selection_rect(Position::new(10, 2), Position::new(4, 6));
+        assert_eq!(rect, Rect::new(4, 2, 7, 5));
+        assert!(rect.contains(Position::new(10, 6)));
+    }
+}